@@ -0,0 +1,87 @@
+//! Throughput of deframing a long random stream, fed in chunks of various sizes
+
+use core::slice::Iter;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use deframe::Deframer;
+
+const FRAME_END: u8 = 0x0A;
+const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+/// The length of the stream, and the capacity of the deframers
+const STREAM_LEN: usize = 64 * 1024;
+const N: usize = 256;
+
+/// Returns a deframer that trusts `GET_FRAME_END` to find the first frame end, as it searches
+/// forward
+fn deframer() -> Deframer<N> {
+    let mut deframer = Deframer::new();
+    deframer.set_forward_search(true);
+    deframer
+}
+
+/// Returns a random stream whose frames average 32 bytes and are never longer than `N`
+fn random_stream() -> Vec<u8> {
+    let mut state: u32 = 0x2545_F491;
+    let mut since_frame_end = 0;
+    (0..STREAM_LEN)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            since_frame_end += 1;
+            if state & 0x1F == 0 || since_frame_end == N {
+                since_frame_end = 0;
+                FRAME_END
+            } else {
+                (state >> 8) as u8 | 0x80
+            }
+        })
+        .collect()
+}
+
+fn throughput(c: &mut Criterion) {
+    let stream = random_stream();
+    let mut group = c.benchmark_group("throughput");
+    group.throughput(Throughput::Bytes(STREAM_LEN as u64));
+
+    for chunk_len in [1, 16, 64, 256] {
+        group.bench_with_input(BenchmarkId::new("deframe", chunk_len), &chunk_len, |b, &chunk_len| {
+            b.iter(|| {
+                let mut deframer = deframer();
+                for chunk in stream.chunks(chunk_len) {
+                    let mut frame = deframer.deframe(chunk, GET_FRAME_END).unwrap();
+                    while !frame.is_empty() {
+                        black_box(&frame);
+                        frame = deframer.deframe(&[], GET_FRAME_END).unwrap();
+                    }
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("next_frame", chunk_len), &chunk_len, |b, &chunk_len| {
+            b.iter(|| {
+                let mut deframer = deframer();
+                for mut chunk in stream.chunks(chunk_len) {
+                    while let Some(frame) = deframer.next_frame(core::mem::take(&mut chunk), GET_FRAME_END).unwrap() {
+                        black_box(&frame);
+                    }
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("deframe_each", chunk_len), &chunk_len, |b, &chunk_len| {
+            b.iter(|| {
+                let mut deframer = deframer();
+                for chunk in stream.chunks(chunk_len) {
+                    deframer.deframe_each(chunk, GET_FRAME_END, |frame| {
+                        black_box(frame);
+                    })
+                    .unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+//! Feeds a stream to the deframer in arbitrary chunks, checking that the frames emitted add up to
+//! the stream up to its last frame end, with nothing lost or duplicated
+
+#![no_main]
+
+use deframe::Deframer;
+use libfuzzer_sys::fuzz_target;
+
+const FRAME_END: u8 = 0x0A;
+const N: usize = 64;
+
+fuzz_target!(|input: (Vec<u8>, Vec<u8>)| {
+    let (chunk_lens, stream) = input;
+
+    // Frames that don't fit are errors rather than lost data, so they are out of scope here
+    if stream.split_inclusive(|&x| x == FRAME_END).any(|frame| frame.len() > N) {
+        return;
+    }
+
+    let mut deframer = Deframer::<N, u8, { 2 * N }>::new();
+    let mut out = Vec::new();
+    let mut rest = &stream[..];
+    for &chunk_len in chunk_lens.iter().cycle().take(stream.len() + 1) {
+        let (chunk, next) = rest.split_at((chunk_len as usize % (N + 1)).min(rest.len()));
+        let mut chunk = chunk;
+        while let Some(frame) = deframer.next_frame(std::mem::take(&mut chunk), |iter| iter.position(|&x| x == FRAME_END)).unwrap() {
+            out.extend_from_slice(&frame);
+        }
+        rest = next;
+    }
+    // Whatever the chunk lengths didn't reach is fed in one go
+    deframer.deframe_each(rest, |iter| iter.position(|&x| x == FRAME_END), |frame| out.extend_from_slice(frame)).unwrap();
+
+    let last_end = stream.iter().rposition(|&x| x == FRAME_END).map_or(0, |pos| pos + 1);
+    assert_eq!(out, stream[0..last_end]);
+    assert_eq!(deframer.remainder(), &stream[last_end..]);
+});
@@ -0,0 +1,122 @@
+//! Framing on boundaries of varying width, found by a callback that returns the whole extent of
+//! each boundary rather than just where it ends
+
+use core::ops::Range;
+
+use crate::{finish_frame, DeframeError, Deframer, Element, Frame, FrameEnd};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Like [`Deframer::deframe`], but `get_boundary` returns the range of the boundary that ends
+    /// a frame, relative to the start of the iterator it is given, rather than the position of a
+    /// single element. Each frame ends with its whole boundary, which is stripped or carried as
+    /// configured with [`Deframer::set_delimiter_policy`], and a checksum is expected just before
+    /// it.
+    ///
+    /// A boundary may be split across data frames, in which case only its end may be found in
+    /// the data. Once a frame end is found `get_boundary` is therefore asked again for the
+    /// boundary ending the assembled frame, so that its full width is known.
+    ///
+    /// A boundary that is empty, reaches past the end of the iterator, or isn't found again at
+    /// the end of its frame is an [`DeframeError::InvalidFrameEnd`] error.
+    pub fn deframe_boundary(&mut self, data_frame: &[T], get_boundary: impl FnMut(&mut core::slice::Iter<T>) -> Option<Range<usize>>) -> Result<Frame<N, T>, DeframeError> {
+        Ok(self.next_frame_boundary(data_frame, get_boundary)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_boundary`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a boundary stripped down to an empty frame can be told apart
+    pub fn next_frame_boundary(&mut self, data_frame: &[T], mut get_boundary: impl FnMut(&mut core::slice::Iter<T>) -> Option<Range<usize>>) -> Result<Option<Frame<N, T>>, DeframeError> {
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::RawUnknown, |iter| {
+            // An empty boundary ends nothing, so it is reported as out of range
+            get_boundary(iter).map(|boundary| if boundary.is_empty() { usize::MAX } else { boundary.end - 1 })
+        })?;
+        let len = match len {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        let end_len = match get_boundary(&mut data[0..len].iter()) {
+            Some(boundary) if boundary.end == len && !boundary.is_empty() => boundary.len(),
+            _ => return Err(self.drop_frame(len, DeframeError::InvalidFrameEnd)),
+        };
+        if self.max_frame_len.is_some_and(|max| len - end_len > max) {
+            return Err(self.drop_frame(len, DeframeError::FrameTooLong));
+        }
+        match finish_frame(&mut data, len, end_len, self.checksum, self.transform, self.delimiter_policy, &mut self.carried_end) {
+            Ok(len) => Ok(Some(Frame::new(data, self.count_frame(len)))),
+            Err(error) => Err(self.drop_frame(len, error)),
+        }
+    }
+
+    /// Counts the `len` elements of a frame that failed as dropped, returning `error`
+    pub(crate) fn drop_frame(&mut self, len: usize, error: DeframeError) -> DeframeError {
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(len);
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::Range;
+    use core::slice::Iter;
+
+    use crate::{DeframeError, Deframer, DelimiterPolicy};
+
+    /// Ends a line on `\r\n` or a lone `\n`
+    fn get_line_end(iter: &mut Iter<u8>) -> Option<Range<usize>> {
+        let data = iter.as_slice();
+        let end = data.iter().position(|&x| x == b'\n')?;
+        let start = if end > 0 && data[end - 1] == b'\r' { end - 1 } else { end };
+        Some(start..end + 1)
+    }
+
+    #[test]
+    fn strips_boundaries_of_varying_width() {
+        let mut deframer = Deframer::<16>::new();
+        deframer.set_strip_delimiter(true);
+        let (data, len) = deframer.deframe_boundary(b"ab\r\ncd\nef", get_line_end).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"ab");
+        let (data, len) = deframer.deframe_boundary(b"", get_line_end).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"cd");
+        assert_eq!(deframer.remainder(), b"ef");
+
+        // Split across data frames, with only the `\n` found in the data
+        assert_eq!(deframer.next_frame_boundary(b"\r", get_line_end).unwrap(), None);
+        let (data, len) = deframer.deframe_boundary(b"\n", get_line_end).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"ef");
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn keeps_or_carries_the_whole_boundary() {
+        let mut deframer = Deframer::<16>::new();
+        let (data, len) = deframer.deframe_boundary(b"ab\r\n", get_line_end).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"ab\r\n");
+
+        deframer.set_delimiter_policy(DelimiterPolicy::PrefixNext);
+        let (data, len) = deframer.deframe_boundary(b"cd\r\nef\n", get_line_end).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"cd");
+        let (data, len) = deframer.deframe_boundary(b"", get_line_end).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"\r\nef");
+    }
+
+    #[test]
+    fn does_not_count_the_boundary_against_the_maximum_frame_length() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_max_frame_len(Some(2));
+        assert_eq!(*deframer.deframe_boundary(b"ab\r\n", get_line_end).unwrap(), *b"ab\r\n");
+        assert_eq!(*deframer.deframe_boundary(b"cd\n", get_line_end).unwrap(), *b"cd\n");
+
+        assert_eq!(deframer.deframe_boundary(b"abc\r\n", get_line_end), Err(DeframeError::FrameTooLong));
+        assert_eq!(deframer.dropped_bytes(), 5);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_boundaries() {
+        let mut deframer = Deframer::<16>::new();
+        assert_eq!(deframer.deframe_boundary(b"ab", |_| Some(1..1)), Err(DeframeError::InvalidFrameEnd));
+        deframer.reset();
+        assert_eq!(deframer.deframe_boundary(b"ab", |_| Some(0..3)), Err(DeframeError::InvalidFrameEnd));
+    }
+}
@@ -0,0 +1,153 @@
+//! Framing with both a start and an end marker, where anything outside a start..end pair is
+//! noise to be discarded
+
+use crate::{consume_remainder, first_frame_end, DeframeError, Deframer, Element, Frame, Overflow};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Deframes data where each frame is enclosed by a start marker found by `get_frame_start`
+    /// and an end marker found by `get_frame_end`, returning the payload between them with both
+    /// markers stripped. Data before a start marker is discarded, so it never appears in a frame.
+    ///
+    /// While a frame is in progress the remainder holds its start marker, which counts towards
+    /// the capacity `BUF`. As with [`Deframer::deframe`], the remainder and `data_frame` are
+    /// discarded on error.
+    pub fn deframe_bounded(&mut self, data_frame: &[T], get_frame_start: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Frame<N, T>, DeframeError> {
+        Ok(self.next_frame_bounded(data_frame, get_frame_start, get_frame_end)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_bounded`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a frame with an empty payload can be told apart
+    pub fn next_frame_bounded(&mut self, data_frame: &[T], get_frame_start: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Frame<N, T>>, DeframeError> {
+        self.count_bytes(data_frame.len());
+        self.count_call();
+        let buffered = self.remainder_length + data_frame.len();
+        let data_frame = self.skip_to_frame_end(data_frame, &mut get_frame_end).map_err(|error| self.discard(buffered, error))?;
+        let buffered = self.remainder_length + data_frame.len();
+        self.bounded_frame(data_frame, get_frame_start, get_frame_end)
+            .map_err(|error| self.discard(buffered, error))
+    }
+
+    fn bounded_frame(&mut self, mut data_frame: &[T], mut get_frame_start: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Frame<N, T>>, DeframeError> {
+        if self.remainder_length == 0 {
+            // Not within a frame, so discard everything up to the next start marker
+            match first_frame_end(data_frame, self.forward_search, &mut get_frame_start)? {
+                Some(start) => data_frame = &data_frame[start..data_frame.len()],
+                None => return Ok(None),
+            }
+        }
+
+        // Buffer as much of the data as fits, then look for the end marker after the start marker
+        let buffered = (BUF - self.remainder_length).min(data_frame.len());
+        self.remainder[self.remainder_length..self.remainder_length + buffered].copy_from_slice(&data_frame[0..buffered]);
+        self.remainder_length += buffered;
+        let mut rest = &data_frame[buffered..data_frame.len()];
+
+        let end = match first_frame_end(&self.remainder[1..self.remainder_length], self.forward_search, get_frame_end)? {
+            Some(pos) => pos + 1,
+            // The frame is still incomplete, which is only an error if it has filled the buffer
+            None if rest.is_empty() => return Ok(None),
+            None => return Err(DeframeError::Overflow(Overflow::RemainderExhausted)),
+        };
+
+        // [start...payload...end]
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = end - 1;
+        if len > N {
+            return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+        }
+        data[0..len].copy_from_slice(&self.remainder[1..end]);
+
+        // Keep whatever follows the end marker from the next start marker onwards
+        let next_start = first_frame_end(&self.remainder[end + 1..self.remainder_length], self.forward_search, &mut get_frame_start)?.map_or(self.remainder_length, |pos| end + 1 + pos);
+        consume_remainder(&mut self.remainder, &mut self.remainder_length, next_start);
+
+        // Likewise when the data that didn't fit in the buffer holds the next start marker
+        if self.remainder_length == 0 {
+            match first_frame_end(rest, self.forward_search, &mut get_frame_start)? {
+                Some(start) => rest = &rest[start..rest.len()],
+                None => rest = &[],
+            }
+        }
+
+        let end_pos = self.remainder_length + rest.len();
+        if end_pos > BUF {
+            return Err(DeframeError::Overflow(Overflow::RemainderExhausted));
+        }
+        self.remainder[self.remainder_length..end_pos].copy_from_slice(rest);
+        self.remainder_length = end_pos;
+
+        Ok(Some(Frame::new(data, self.count_frame(len))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use crate::{DeframeError, Deframer, Overflow};
+
+    const STX: u8 = 0x02;
+    const ETX: u8 = 0x03;
+    const GET_FRAME_START: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == STX);
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == ETX);
+
+    #[test]
+    fn strips_noise_and_markers() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_bounded(&[0x10, 0x11, STX, 0x20, 0x21, ETX, 0x12], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x20, 0x21]);
+        assert!(deframer.is_empty());
+
+        // Noise is never buffered
+        let (_data, len) = deframer.deframe_bounded(&[0x13, ETX, 0x14], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn buffers_frames_across_calls() {
+        let mut deframer = Deframer::<8>::new();
+        let (_data, len) = deframer.deframe_bounded(&[0x10, STX, 0x20], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert_eq!(deframer.remainder(), [STX, 0x20]);
+
+        let (data, len) = deframer.deframe_bounded(&[0x21, ETX, 0x11, STX, 0x30], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x20, 0x21]);
+        assert_eq!(deframer.remainder(), [STX, 0x30]);
+
+        let (data, len) = deframer.deframe_bounded(&[ETX], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x30]);
+    }
+
+    #[test]
+    fn strips_noise_that_did_not_fit_in_the_buffer() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_bounded(&[STX, 0x20, ETX, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x20]);
+        assert!(deframer.is_empty());
+
+        let (data, len) = deframer.deframe_bounded(&[0x17, STX, 0x30, ETX], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x30]);
+
+        // The next start marker is kept when it is among the data that didn't fit
+        let (data, len) = deframer.deframe_bounded(&[STX, 0x20, ETX, 0x11, 0x12, 0x13, 0x14, 0x15, STX, 0x40], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x20]);
+        assert_eq!(deframer.remainder(), [STX, 0x40]);
+    }
+
+    #[test]
+    fn accepts_empty_payloads() {
+        let mut deframer = Deframer::<8>::new();
+        let (_data, len) = deframer.deframe_bounded(&[STX, ETX], GET_FRAME_START, GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn overflows_when_the_end_marker_never_arrives() {
+        let mut deframer = Deframer::<4>::new();
+        let result = deframer.deframe_bounded(&[STX, 0x20, 0x21, 0x22, 0x23], GET_FRAME_START, GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert!(deframer.is_empty());
+    }
+}
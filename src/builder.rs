@@ -0,0 +1,114 @@
+//! Chainable configuration of a [`Deframer`]
+
+use crate::{store_delimiters, Deframer, DelimiterPolicy, Element};
+
+/// Builds a [`Deframer`] with the options set by chaining its methods, ending with
+/// [`DeframerBuilder::build`]. Options that aren't set keep the defaults of [`Deframer::new`].
+#[derive(Clone, Copy)]
+pub struct DeframerBuilder<const N: usize, T = u8, const BUF: usize = N> {
+    deframer: Deframer<N, T, BUF>,
+}
+
+impl<const N: usize, T: Element, const BUF: usize> DeframerBuilder<N, T, BUF> {
+    /// Starts building a deframer with the default options
+    pub const fn new() -> Self {
+        Self { deframer: Deframer::new() }
+    }
+
+    /// Ends frames on `delimiter` when using [`Deframer::deframe_delimited`]
+    pub fn delimiter(self, delimiter: T) -> Self {
+        self.delimiters(&[delimiter])
+    }
+
+    /// Ends frames on any of `delimiters` when using [`Deframer::deframe_delimited`], as with
+    /// [`Deframer::with_delimiters`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`crate::MAX_DELIMITERS`] delimiters are given.
+    pub fn delimiters(mut self, delimiters: &[T]) -> Self {
+        self.deframer.delimiters = store_delimiters(delimiters);
+        self.deframer.delimiter_count = delimiters.len();
+        self
+    }
+
+    /// Sets whether the frame end is excluded from returned frames, see
+    /// [`Deframer::set_strip_delimiter`]
+    pub fn strip_delimiter(mut self, strip_delimiter: bool) -> Self {
+        self.deframer.set_strip_delimiter(strip_delimiter);
+        self
+    }
+
+    /// Sets where the frame end of each frame ends up, see [`Deframer::set_delimiter_policy`]
+    pub fn delimiter_policy(mut self, delimiter_policy: DelimiterPolicy) -> Self {
+        self.deframer.set_delimiter_policy(delimiter_policy);
+        self
+    }
+
+    /// Sets the longest frame that is accepted, see [`Deframer::set_max_frame_len`]
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.deframer.set_max_frame_len(Some(max_frame_len));
+        self
+    }
+
+    /// Sets the shortest frame that is returned, see [`Deframer::set_min_frame_len`]
+    pub fn min_frame_len(mut self, min_frame_len: usize) -> Self {
+        self.deframer.set_min_frame_len(Some(min_frame_len));
+        self
+    }
+
+    /// Sets whether `get_frame_end` is trusted to return the first frame end, see
+    /// [`Deframer::set_forward_search`]
+    pub fn forward_search(mut self, forward_search: bool) -> Self {
+        self.deframer.set_forward_search(forward_search);
+        self
+    }
+
+    /// Sets whether a remainder that fills the whole buffer is an error, see
+    /// [`Deframer::set_strict_full_buffer`]
+    pub fn strict_full_buffer(mut self, strict_full_buffer: bool) -> Self {
+        self.deframer.set_strict_full_buffer(strict_full_buffer);
+        self
+    }
+
+    /// Returns the configured deframer
+    pub fn build(self) -> Deframer<N, T, BUF> {
+        self.deframer
+    }
+}
+
+impl<const N: usize, const BUF: usize> DeframerBuilder<N, u8, BUF> {
+    /// Verifies every frame against `checksum`, see [`Deframer::set_checksum`]
+    pub fn checksum(mut self, checksum: fn(payload: &[u8]) -> u16) -> Self {
+        self.deframer.set_checksum(Some(checksum));
+        self
+    }
+}
+
+impl<const N: usize, T: Element, const BUF: usize> Default for DeframerBuilder<N, T, BUF> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, DeframerBuilder};
+
+    #[test]
+    fn builds_a_configured_deframer() {
+        let mut deframer = DeframerBuilder::<8>::new().delimiter(b'\n').strip_delimiter(true).max_frame_len(3).build();
+
+        let (data, len) = deframer.deframe_delimited(b"abc\n").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"abc");
+
+        assert_eq!(deframer.deframe_delimited(b"abcd\n"), Err(DeframeError::FrameTooLong));
+    }
+
+    #[test]
+    fn builds_a_default_deframer() {
+        let mut deframer = DeframerBuilder::<8>::default().build();
+        let (data, len) = deframer.deframe_delimited(&[0x01, 0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x00]);
+    }
+}
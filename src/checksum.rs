@@ -0,0 +1,115 @@
+//! Checksum validation of completed frames, where each frame carries a CRC-16 (or any other
+//! 16 bit checksum) just before its frame end
+
+use crate::{DeframeError, Deframer, Element};
+
+/// The number of elements taken up by the checksum
+const CHECKSUM_LEN: usize = 2;
+
+/// How to compute and read back the checksum of a frame
+#[derive(Clone, Copy)]
+pub(crate) struct Checksum<T> {
+    /// Computes the checksum over a payload
+    compute: fn(payload: &[T]) -> u16,
+    /// Reads the checksum carried by the frame
+    expected: fn(checksum: &[T]) -> u16,
+}
+
+impl<T: Element> Checksum<T> {
+    /// Verifies the checksum at the end of `body`, returning the length of the payload before it.
+    /// A body too short to hold a checksum is malformed.
+    pub(crate) fn verify(&self, body: &[T]) -> Result<usize, DeframeError> {
+        if body.len() < CHECKSUM_LEN {
+            return Err(DeframeError::MalformedFrame);
+        }
+        let (payload, checksum) = body.split_at(body.len() - CHECKSUM_LEN);
+        let expected = (self.expected)(checksum);
+        let computed = (self.compute)(payload);
+        if expected != computed {
+            return Err(DeframeError::ChecksumMismatch { expected, computed });
+        }
+        Ok(payload.len())
+    }
+}
+
+impl<const N: usize, const BUF: usize> Deframer<N, u8, BUF> {
+    /// Sets a checksum to verify every completed frame against, or disables verification with
+    /// `None`. Frames are expected to carry the checksum as two big-endian bytes just before their
+    /// frame end, computed by `checksum` over the payload before it.
+    ///
+    /// A frame that fails verification is dropped with `Err(DeframeError::ChecksumMismatch)` and
+    /// counted in [`Deframer::dropped_bytes`], keeping any bytes buffered after it, as is a frame
+    /// too short to carry a checksum with `Err(DeframeError::MalformedFrame)`. The checksum is
+    /// removed from frames that pass, so only the validated payload is returned.
+    pub fn set_checksum(&mut self, checksum: Option<fn(payload: &[u8]) -> u16>) {
+        self.checksum = checksum.map(|compute| Checksum {
+            compute,
+            expected: |checksum| u16::from_be_bytes([checksum[0], checksum[1]]),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use crate::{DeframeError, Deframer};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    /// A simple additive checksum, standing in for a CRC
+    fn sum(payload: &[u8]) -> u16 {
+        payload.iter().map(|&x| x as u16).sum()
+    }
+
+    #[test]
+    fn verifies_and_removes_the_checksum() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_checksum(Some(sum));
+
+        let (data, len) = deframer.deframe(&[0x01, 0x02, 0x00, 0x03, FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02, FRAME_END]);
+
+        deframer.set_strip_delimiter(true);
+        let (data, len) = deframer.deframe(&[0x7F, 0x00, 0x7F, FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x7F]);
+    }
+
+    #[test]
+    fn rejects_mismatched_checksums() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_checksum(Some(sum));
+
+        let result = deframer.deframe(&[0x01, 0x02, 0x00, 0x04, FRAME_END, 0x05], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::ChecksumMismatch { expected: 0x04, computed: 0x03 }));
+        assert_eq!(deframer.dropped_bytes(), 5);
+        assert_eq!(deframer.remainder(), [0x05]);
+    }
+
+    #[test]
+    fn verifies_decoded_cobs_frames() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_checksum(Some(sum));
+
+        let (data, len) = deframer.deframe_cobs(&[0x02, 0x11, 0x02, 0x11, 0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x11]);
+
+        let result = deframer.deframe_cobs(&[0x02, 0x11, 0x02, 0x12, 0x00]);
+        assert_eq!(result, Err(DeframeError::ChecksumMismatch { expected: 0x12, computed: 0x11 }));
+    }
+
+    #[test]
+    fn rejects_frames_too_short_for_a_checksum() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_checksum(Some(sum));
+
+        let result = deframer.deframe(&[0x01, FRAME_END, 0x02], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::MalformedFrame));
+        assert_eq!(deframer.dropped_bytes(), 2);
+        assert_eq!(deframer.remainder(), [0x02]);
+
+        assert_eq!(deframer.deframe(&[FRAME_END], GET_FRAME_END), Err(DeframeError::MalformedFrame));
+        assert_eq!(deframer.deframe_cobs(&[0x02, 0x11, 0x00]), Err(DeframeError::MalformedFrame));
+    }
+}
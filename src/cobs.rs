@@ -0,0 +1,133 @@
+//! Consistent Overhead Byte Stuffing (COBS) framing, where frames are delimited by a zero byte
+//! and any zeros in the payload are encoded away
+
+use crate::{DeframeError, Deframer, Frame, FrameEnd, Overflow};
+
+/// The COBS frame delimiter
+const COBS_DELIMITER: u8 = 0x00;
+
+impl<const N: usize, const BUF: usize> Deframer<N, u8, BUF> {
+    /// Deframes COBS encoded data, splitting frames on the next zero byte and decoding each one
+    /// into the returned buffer. The returned length is that of the decoded payload, with the
+    /// overhead bytes and delimiter stripped.
+    ///
+    /// If a checksum is set it is verified against, and removed from, the decoded payload.
+    ///
+    /// A frame that fails to decode is counted in [`Deframer::dropped_bytes`], but unlike an
+    /// overflow the bytes buffered after it are kept.
+    pub fn deframe_cobs(&mut self, data_frame: &[u8]) -> Result<Frame<N>, DeframeError> {
+        Ok(self.next_frame_cobs(data_frame)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_cobs`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a frame with an empty payload can be told apart
+    pub fn next_frame_cobs(&mut self, data_frame: &[u8]) -> Result<Option<Frame<N>>, DeframeError> {
+        let encoded_len = self.deframe_frame(data_frame, None, FrameEnd::Raw(1), |iter| iter.position(|&x| x == COBS_DELIMITER))?;
+
+        let mut data: [u8; N] = [0; N];
+        let encoded_len = match encoded_len {
+            Some(encoded_len) => encoded_len,
+            None => return Ok(None),
+        };
+
+        // Decode the frame without its trailing delimiter, then verify the decoded payload
+        let len = decode(&self.frame[0..encoded_len - 1], &mut data).and_then(|len| match self.checksum {
+            Some(checksum) => checksum.verify(&data[0..len]),
+            None => Ok(len),
+        });
+        let len = len.map_err(|error| {
+            self.dropped_bytes = self.dropped_bytes.wrapping_add(encoded_len);
+            self.record_error(error, data_frame.len(), 0)
+        })?;
+        data[len..N].fill(0);
+        Ok(Some(Frame::new(data, self.count_frame(len))))
+    }
+}
+
+/// Decodes a single COBS encoded frame (excluding the delimiter) into `out`, returning the
+/// decoded length
+fn decode(encoded: &[u8], out: &mut [u8]) -> Result<usize, DeframeError> {
+    let mut read = 0;
+    let mut write = 0;
+
+    while read < encoded.len() {
+        let code = encoded[read] as usize;
+        if code == 0 {
+            return Err(DeframeError::InvalidEncoding);
+        }
+        read += 1;
+
+        // The code points at the next zero, which must not run past the end of the frame
+        let block_end = read + code - 1;
+        if block_end > encoded.len() {
+            return Err(DeframeError::InvalidEncoding);
+        }
+        let block = &encoded[read..block_end];
+        if block.contains(&COBS_DELIMITER) {
+            return Err(DeframeError::InvalidEncoding);
+        }
+        if write + block.len() > out.len() {
+            return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+        }
+        out[write..write + block.len()].copy_from_slice(block);
+        write += block.len();
+        read = block_end;
+
+        // A maximum length block has no implied zero, nor does the final block
+        if code != 0xFF && read < encoded.len() {
+            if write >= out.len() {
+                return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+            }
+            out[write] = 0x00;
+            write += 1;
+        }
+    }
+
+    Ok(write)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, Deframer};
+
+    #[test]
+    fn decodes_cobs_frames() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_cobs(&[0x03, 0x11, 0x22, 0x02, 0x33, 0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x11, 0x22, 0x00, 0x33]);
+
+        // The delimiter arrives in a later data frame
+        let (_data, len) = deframer.deframe_cobs(&[0x01, 0x01]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (data, len) = deframer.deframe_cobs(&[0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x00]);
+
+        let (_data, len) = deframer.deframe_cobs(&[0x01, 0x00]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn decodes_maximum_length_blocks() {
+        let mut encoded = [0x01; 257];
+        encoded[0] = 0xFF;
+        encoded[255] = 0x01;
+        encoded[256] = 0x00;
+
+        let mut deframer = Deframer::<300>::new();
+        let (data, len) = deframer.deframe_cobs(&encoded).unwrap().into_parts();
+        assert_eq!(len, 254);
+        assert!(data[0..len].iter().all(|&x| x == 0x01));
+    }
+
+    #[test]
+    fn rejects_invalid_encoding() {
+        let mut deframer = Deframer::<8>::new();
+        // The first code points past the end of the frame
+        let result = deframer.deframe_cobs(&[0x05, 0x11, 0x22, 0x00, 0x02]);
+        assert_eq!(result, Err(DeframeError::InvalidEncoding));
+        assert_eq!(deframer.dropped_bytes(), 4);
+        assert_eq!(deframer.remainder(), [0x02]);
+        assert_eq!(deframer.last_error().map(|context| context.offset), Some(3));
+    }
+}
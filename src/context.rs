@@ -0,0 +1,68 @@
+//! Where in the data the last error was detected, for diagnosing a misbehaving sender
+
+use crate::{DeframeError, Deframer, Element, Overflow};
+
+/// A [`DeframeError`] along with the position in the data frame at which it was detected
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ErrorContext {
+    /// The error that was returned
+    pub error: DeframeError,
+    /// The position within the data frame of the call that failed. This is the first element that
+    /// didn't fit for [`Overflow::RemainderExhausted`], and otherwise the frame end of the frame
+    /// that failed, or zero if it was already buffered before the call.
+    pub offset: usize,
+}
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Returns the last error returned by a delimiter based method, with where in its data frame
+    /// it was detected, or `None` if there has been none since construction or
+    /// [`Deframer::reset`]
+    pub fn last_error(&self) -> Option<ErrorContext> {
+        self.last_error
+    }
+
+    /// Records `error` as detected in a data frame of `length` elements, where `room` of them
+    /// fit in the remainder at the start of the call
+    pub(crate) fn record_error(&mut self, error: DeframeError, length: usize, room: usize) -> DeframeError {
+        let offset = match error {
+            DeframeError::Overflow(Overflow::RemainderExhausted) => room,
+            _ => self.consumed.max(1) - 1,
+        };
+        self.last_error = Some(ErrorContext { error, offset: offset.min(length.max(1) - 1) });
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use super::ErrorContext;
+    use crate::{DeframeError, Deframer, Overflow};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn records_where_errors_were_detected() {
+        let mut deframer = Deframer::<4>::new();
+        assert_eq!(deframer.last_error(), None);
+
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        let error = DeframeError::Overflow(Overflow::RemainderExhausted);
+        assert_eq!(deframer.deframe(&[0x03, 0x04, 0x05, 0x06], GET_FRAME_END), Err(error));
+        assert_eq!(deframer.last_error(), Some(ErrorContext { error, offset: 2 }));
+
+        let error = DeframeError::Overflow(Overflow::SingleFrameTooLarge);
+        assert_eq!(deframer.deframe(&[0x00, 0x01, 0x02, 0x03, 0x04, FRAME_END, 0x05], GET_FRAME_END), Err(error));
+        assert_eq!(deframer.last_error(), Some(ErrorContext { error, offset: 5 }));
+
+        deframer.set_max_frame_len(Some(1));
+        assert_eq!(deframer.deframe(&[0x06, FRAME_END], GET_FRAME_END), Err(DeframeError::FrameTooLong));
+        assert_eq!(deframer.last_error(), Some(ErrorContext { error: DeframeError::FrameTooLong, offset: 1 }));
+
+        deframer.reset();
+        assert_eq!(deframer.last_error(), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Line framing for CR LF terminated text, as used by most serial consoles
+
+use crate::{consume_remainder, DeframeError, Deframer, Frame, FrameEnd};
+
+const CR: u8 = b'\r';
+const LF: u8 = b'\n';
+
+impl<const N: usize, const BUF: usize> Deframer<N, u8, BUF> {
+    /// Deframes lines terminated by CR LF, treating the pair as a single boundary which is
+    /// stripped from the returned frame. A lone CR or LF also ends a line, so a sender that only
+    /// uses one of them never stalls the deframer.
+    ///
+    /// When a CR is the last byte of a data frame, a LF at the start of the next data frame is
+    /// taken as the rest of that boundary rather than producing an empty line.
+    pub fn deframe_crlf(&mut self, data_frame: &[u8]) -> Result<Frame<N>, DeframeError> {
+        Ok(self.next_frame_crlf(data_frame)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_crlf`], but returns `Ok(None)` while no complete line is
+    /// available, so that an empty line can be told apart
+    pub fn next_frame_crlf(&mut self, mut data_frame: &[u8]) -> Result<Option<Frame<N>>, DeframeError> {
+        let mut skipped = 0;
+        if !data_frame.is_empty() {
+            if self.after_cr && data_frame[0] == LF {
+                data_frame = &data_frame[1..data_frame.len()];
+                skipped = 1;
+                self.count_bytes(1);
+            }
+            self.after_cr = false;
+        }
+
+        let mut data: [u8; N] = [0; N];
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Raw(1), |iter| iter.position(|&x| x == CR || x == LF));
+        self.consumed += skipped;
+        let len = match len? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        let mut payload_len = len - 1;
+        if data[payload_len] == LF && payload_len > 0 && data[payload_len - 1] == CR {
+            payload_len -= 1;
+        } else if data[payload_len] == CR {
+            // The LF completing this boundary may follow in the remainder or the next data frame
+            if self.remainder_length == 0 {
+                self.after_cr = true;
+            } else if self.remainder[0] == LF {
+                consume_remainder(&mut self.remainder, &mut self.remainder_length, 1);
+                self.consumed += 1;
+            }
+        }
+        data[payload_len..len].fill(0);
+
+        Ok(Some(Frame::new(data, self.count_frame(payload_len))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CR, LF};
+    use crate::{Deframer, Frame};
+
+    #[test]
+    fn strips_crlf() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_crlf(&[0x01, CR, LF, 0x02]).unwrap().into_parts();
+        assert_eq!(data, [0x01, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(len, 1);
+        assert_eq!(deframer.remainder(), [0x02]);
+
+        let (data, len) = deframer.deframe_crlf(&[CR, LF]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn joins_crlf_split_across_data_frames() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_crlf(&[0x01, CR]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01]);
+
+        // An empty read doesn't lose track of the pending LF
+        let (_data, len) = deframer.deframe_crlf(&[]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (_data, len) = deframer.deframe_crlf(&[LF, 0x02]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert_eq!(deframer.remainder(), [0x02]);
+    }
+
+    #[test]
+    fn ends_lines_on_a_lone_cr_or_lf() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_crlf(&[0x01, LF]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01]);
+
+        let (data, len) = deframer.deframe_crlf(&[0x02, CR, 0x03]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02]);
+
+        // Consecutive line endings are still empty lines
+        let (data, len) = deframer.deframe_crlf(&[LF]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x03]);
+        let (_data, len) = deframer.deframe_crlf(&[CR, LF]).unwrap().into_parts();
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn distinguishes_no_line_from_an_empty_line() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.next_frame_crlf(&[0x01]).unwrap(), None);
+        assert_eq!(deframer.next_frame_crlf(&[CR, LF]).unwrap().map(Frame::into_parts), Some(([0x01, 0, 0, 0, 0, 0, 0, 0], 1)));
+        assert_eq!(deframer.next_frame_crlf(&[CR, LF]).unwrap().map(Frame::into_parts), Some(([0; 8], 0)));
+        assert_eq!(deframer.next_frame_crlf(&[]).unwrap(), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Framing where a delimiter within the payload is escaped by doubling it, so that only a lone
+//! delimiter ends a frame
+
+use crate::{DeframeError, Deframer, Element, Frame, FrameEnd};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Deframes data where a frame ends with a lone `delimiter`, and a doubled delimiter is a
+    /// literal one within the payload. The returned frame has each doubled delimiter collapsed to
+    /// one element and the delimiter ending it stripped.
+    ///
+    /// A delimiter at the end of the data can't be told apart from the first half of a doubled
+    /// delimiter until the next element arrives, so such a frame is only returned by the next
+    /// call, which may be given just that element.
+    pub fn deframe_doubled(&mut self, data_frame: &[T], delimiter: T) -> Result<Frame<N, T>, DeframeError> {
+        Ok(self.next_frame_doubled(data_frame, delimiter)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_doubled`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a frame with an empty payload can be told apart
+    pub fn next_frame_doubled(&mut self, data_frame: &[T], delimiter: T) -> Result<Option<Frame<N, T>>, DeframeError> {
+        // The remainder starts at a frame start, so pairing its delimiters from the front shows
+        // whether it ends with the first half of a doubled delimiter that continues in the data
+        let remainder_end = self.remainder().as_ptr_range().end;
+        let split_pair = unpaired_end(self.remainder(), delimiter) && data_frame.first() == Some(&delimiter);
+        let data_start = data_frame.as_ptr();
+        let next = data_frame.first().copied();
+
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Raw(1), |iter| {
+            let slice = iter.as_slice();
+            // Only the remainder is followed by the data, where a lone delimiter at its end is
+            // decided by the first element of the data
+            let followed_by = if slice.as_ptr_range().end == remainder_end { next } else { None };
+            let start = (split_pair && slice.as_ptr() == data_start) as usize;
+            lone_delimiter(&slice[start.min(slice.len())..slice.len()], delimiter, followed_by).map(|pos| start + pos)
+        })?;
+        let len = match len {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        let len = collapse_pairs(&mut data, len - 1, delimiter);
+        Ok(Some(Frame::new(data, self.count_frame(len))))
+    }
+}
+
+/// Returns the position of the first lone `delimiter` in `data`, pairing delimiters from the
+/// front. A delimiter at the end is only lone if `followed_by` is known not to be a delimiter.
+fn lone_delimiter<T: Element>(data: &[T], delimiter: T, followed_by: Option<T>) -> Option<usize> {
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] == delimiter {
+            match data.get(i + 1).copied().or(followed_by) {
+                Some(x) if x == delimiter => i += 1,
+                Some(_) => return Some(i),
+                None => return None,
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Returns true if `data` ends with a delimiter that isn't paired with the one before it, which
+/// is when it ends with an odd run of delimiters as they are paired from the start of the run
+fn unpaired_end<T: Element>(data: &[T], delimiter: T) -> bool {
+    data.iter().rev().take_while(|&&x| x == delimiter).count() % 2 == 1
+}
+
+/// Collapses each doubled delimiter in the first `len` elements of `data` to a single one,
+/// zeroing everything after the collapsed payload, and returns its length
+fn collapse_pairs<T: Element>(data: &mut [T], len: usize, delimiter: T) -> usize {
+    let mut read = 0;
+    let mut write = 0;
+    while read < len {
+        data[write] = data[read];
+        read += if data[read] == delimiter { 2 } else { 1 };
+        write += 1;
+    }
+    let end = data.len();
+    data[write..end].fill(T::ZERO);
+    write
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Deframer, Frame};
+
+    const DLE: u8 = 0x10;
+
+    #[test]
+    fn collapses_doubled_delimiters() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_doubled(&[0x01, DLE, DLE, 0x02, DLE, 0x03], DLE).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, DLE, 0x02]);
+        assert_eq!(deframer.remainder(), [0x03]);
+
+        // A doubled delimiter just before the one ending the frame
+        let (data, len) = deframer.deframe_doubled(&[DLE, DLE, DLE, 0x04], DLE).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x03, DLE]);
+        assert_eq!(deframer.remainder(), [0x04]);
+    }
+
+    #[test]
+    fn waits_for_the_element_after_a_trailing_delimiter() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.next_frame_doubled(&[0x01, DLE], DLE).unwrap(), None);
+        assert_eq!(deframer.next_frame_doubled(&[], DLE).unwrap(), None);
+        let (data, len) = deframer.deframe_doubled(&[0x02], DLE).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01]);
+        assert_eq!(deframer.remainder(), [0x02]);
+
+        // The pair is completed at the start of the next data frame, and followed by a lone one
+        assert_eq!(deframer.next_frame_doubled(&[DLE], DLE).unwrap(), None);
+        let (data, len) = deframer.deframe_doubled(&[DLE, DLE, 0x04], DLE).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, DLE]);
+        assert_eq!(deframer.remainder(), [0x04]);
+    }
+
+    #[test]
+    fn returns_empty_frames() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.next_frame_doubled(&[DLE, 0x01], DLE).unwrap().map(Frame::into_parts), Some(([0; 8], 0)));
+        assert_eq!(deframer.remainder(), [0x01]);
+    }
+
+    #[test]
+    fn pairs_delimiters_split_across_data_frames() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.next_frame_doubled(&[0x01, DLE], DLE).unwrap(), None);
+        let (data, len) = deframer.deframe_doubled(&[DLE, 0x02, DLE, 0x03], DLE).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, DLE, 0x02]);
+        assert_eq!(deframer.remainder(), [0x03]);
+
+        // One element per call, with a doubled delimiter then a lone one
+        let mut frames = 0;
+        for &x in &[DLE, DLE, 0x04, DLE, DLE, DLE, 0x05] {
+            if let Some((data, len)) = deframer.next_frame_doubled(&[x], DLE).unwrap().map(Frame::into_parts) {
+                assert_eq!(data[0..len], [0x03, DLE, 0x04, DLE]);
+                frames += 1;
+            }
+        }
+        assert_eq!(frames, 1);
+        assert_eq!(deframer.remainder(), [0x05]);
+    }
+}
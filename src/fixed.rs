@@ -0,0 +1,70 @@
+//! Fixed-length framing, where every frame is the same number of elements and there is no
+//! delimiter at all
+
+use crate::{DeframeError, Deframer, Element, Frame};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Creates a deframer that splits data into frames of exactly `fixed_len` elements when using
+    /// [`Deframer::deframe_fixed`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fixed_len` is zero.
+    pub const fn with_fixed_len(fixed_len: usize) -> Self {
+        assert!(fixed_len > 0, "fixed frame length of zero");
+        let mut deframer = Self::new();
+        deframer.fixed_len = fixed_len;
+        deframer
+    }
+
+    /// Deframes data made up of fixed-length records, returning the next complete record once
+    /// enough data has arrived and buffering the partial tail. This is length-prefixed deframing
+    /// where every frame declares the same length, see [`Deframer::deframe_length_prefixed`].
+    ///
+    /// Returns `Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge))` if the frame length
+    /// exceeds `N`, or `Err(DeframeError::NotConfigured)`, leaving `data_frame` unbuffered, if the
+    /// deframer wasn't constructed with [`Deframer::with_fixed_len`].
+    pub fn deframe_fixed(&mut self, data_frame: &[T]) -> Result<Frame<N, T>, DeframeError> {
+        let fixed_len = self.fixed_len;
+        if fixed_len == 0 {
+            return Err(DeframeError::NotConfigured);
+        }
+        self.deframe_length_prefixed(data_frame, |_| Some(fixed_len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, Deframer, Overflow};
+
+    #[test]
+    fn deframes_fixed_length_records() {
+        let mut deframer = Deframer::<8>::with_fixed_len(3);
+        let (data, len) = deframer.deframe_fixed(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02, 0x03]);
+        assert_eq!(deframer.remainder(), [0x04, 0x05, 0x06, 0x07]);
+
+        // A whole record is already buffered
+        let (data, len) = deframer.deframe_fixed(&[0x08]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, 0x05, 0x06]);
+        assert_eq!(deframer.remainder(), [0x07, 0x08]);
+
+        let (data, len) = deframer.deframe_fixed(&[0x09]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x07, 0x08, 0x09]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn overflows_if_the_frame_length_exceeds_the_capacity() {
+        let mut deframer = Deframer::<2>::with_fixed_len(3);
+        let result = deframer.deframe_fixed(&[0x01]);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+    }
+
+    #[test]
+    fn rejects_a_deframer_without_a_fixed_length() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.deframe_fixed(&[0x01, 0x02]), Err(DeframeError::NotConfigured));
+        assert!(deframer.is_empty());
+    }
+}
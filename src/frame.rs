@@ -0,0 +1,125 @@
+//! A frame returned by value, which carries its own length
+
+use core::ops::Deref;
+
+use crate::Element;
+
+/// A frame of up to `N` elements, as returned by [`Deframer::deframe`] and every other method
+/// producing frames. It derefs to the valid part of its buffer only, so nothing past the end of
+/// the frame can be read by mistake.
+///
+/// Methods such as [`Deframer::deframe`] return an empty frame when no frame end was found, but
+/// a frame can also be empty in its own right, such as a lone frame end stripped with
+/// [`DelimiterPolicy::Strip`]. Use [`Deframer::next_frame`] to tell the two apart.
+///
+/// [`Deframer::deframe`]: crate::Deframer::deframe
+/// [`Deframer::next_frame`]: crate::Deframer::next_frame
+/// [`DelimiterPolicy::Strip`]: crate::DelimiterPolicy::Strip
+#[derive(Clone, Copy)]
+pub struct Frame<const N: usize, T = u8> {
+    data: [T; N],
+    len: usize,
+}
+
+impl<const N: usize, T> Frame<N, T> {
+    /// Creates a frame from the first `len` elements of `data`, where `len` is at most `N`
+    pub(crate) fn new(data: [T; N], len: usize) -> Self {
+        debug_assert!(len <= N, "frame longer than its buffer");
+        Self { data, len }
+    }
+
+    /// Returns the elements of the frame
+    pub fn as_slice(&self) -> &[T] {
+        &self.data[0..self.len]
+    }
+
+    /// Returns the whole buffer and the length of the frame at its start
+    pub fn into_parts(self) -> ([T; N], usize) {
+        (self.data, self.len)
+    }
+}
+
+impl<const N: usize, T: Element> Frame<N, T> {
+    /// Creates a frame holding a copy of `data`, which is at most `N` elements long
+    pub(crate) fn from_slice(data: &[T]) -> Self {
+        let mut frame = Self::default();
+        frame.data[0..data.len()].copy_from_slice(data);
+        frame.len = data.len();
+        frame
+    }
+}
+
+impl<const N: usize, T> Deref for Frame<N, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize, T: Element> Default for Frame<N, T> {
+    /// Returns an empty frame, such as to fill an array passed to [`Deframer::deframe_batch`]
+    ///
+    /// [`Deframer::deframe_batch`]: crate::Deframer::deframe_batch
+    fn default() -> Self {
+        Self::new([T::ZERO; N], 0)
+    }
+}
+
+impl<const N: usize, T> AsRef<[T]> for Frame<N, T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize, T> From<Frame<N, T>> for ([T; N], usize) {
+    fn from(frame: Frame<N, T>) -> Self {
+        frame.into_parts()
+    }
+}
+
+impl<const N: usize, T: PartialEq> PartialEq for Frame<N, T> {
+    /// Compares only the elements of the frame, not the rest of the buffer
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize, T: Eq> Eq for Frame<N, T> {}
+
+impl<const N: usize, T: core::fmt::Debug> core::fmt::Debug for Frame<N, T> {
+    /// Shows only the elements of the frame, rather than the whole buffer
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Frame").field(&self.as_slice()).finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize, T: defmt::Format> defmt::Format for Frame<N, T> {
+    /// Shows only the elements of the frame, rather than the whole buffer
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Frame({})", self.as_slice());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use crate::Deframer;
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn derefs_to_the_frame_only() {
+        let mut deframer = Deframer::<8>::new();
+        let frame = deframer.deframe(&[0x01, FRAME_END, 0x02], GET_FRAME_END).unwrap();
+        assert_eq!(*frame, [0x01, FRAME_END]);
+        assert_eq!(frame.len(), 2);
+        assert_eq!(frame.into_parts(), ([0x01, FRAME_END, 0, 0, 0, 0, 0, 0], 2));
+
+        let frame = deframer.deframe(&[0x03], GET_FRAME_END).unwrap();
+        assert!(frame.is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+//! Length-prefixed framing with a binary length header, decoded as configured rather than by a
+//! `get_frame_len` function
+
+use core::convert::TryFrom;
+
+use crate::{DeframeError, Deframer, Frame};
+
+/// The size and byte order of the header declaring the payload length of each frame
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LengthHeader {
+    /// A single byte
+    U8,
+    /// Two bytes, most significant first
+    U16Be,
+    /// Two bytes, least significant first
+    U16Le,
+    /// Four bytes, most significant first
+    U32Be,
+    /// Four bytes, least significant first
+    U32Le,
+}
+
+impl LengthHeader {
+    /// Returns the number of bytes taken up by the header
+    pub const fn size(&self) -> usize {
+        match self {
+            LengthHeader::U8 => 1,
+            LengthHeader::U16Be | LengthHeader::U16Le => 2,
+            LengthHeader::U32Be | LengthHeader::U32Le => 4,
+        }
+    }
+
+    /// Decodes the payload length from the start of `header`, which must hold the whole header
+    fn decode(&self, header: &[u8]) -> u32 {
+        match self {
+            LengthHeader::U8 => header[0] as u32,
+            LengthHeader::U16Be => u16::from_be_bytes([header[0], header[1]]) as u32,
+            LengthHeader::U16Le => u16::from_le_bytes([header[0], header[1]]) as u32,
+            LengthHeader::U32Be => u32::from_be_bytes([header[0], header[1], header[2], header[3]]),
+            LengthHeader::U32Le => u32::from_le_bytes([header[0], header[1], header[2], header[3]]),
+        }
+    }
+}
+
+impl<const N: usize, const BUF: usize> Deframer<N, u8, BUF> {
+    /// Creates a deframer that reads the payload length of each frame from a `header` at its
+    /// start when using [`Deframer::deframe_length_header`]
+    pub const fn with_length_header(header: LengthHeader) -> Self {
+        let mut deframer = Self::new();
+        deframer.length_header = Some(header);
+        deframer
+    }
+
+    /// Deframes data where each frame starts with a header declaring the length of the payload
+    /// that follows it. Once the whole payload has arrived the frame is returned, header
+    /// included, so the payload is the data after the first [`LengthHeader::size`] bytes.
+    ///
+    /// Returns `Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge))` if the frame is
+    /// longer than `N`, or `Err(DeframeError::InvalidLength)` if the declared length is zero or
+    /// too large to represent. As with [`Deframer::deframe`], the remainder and `data_frame` are
+    /// discarded on error, except for `Err(DeframeError::NotConfigured)`, which leaves them
+    /// untouched if the deframer wasn't constructed with [`Deframer::with_length_header`].
+    ///
+    /// [`Overflow::SingleFrameTooLarge`]: crate::Overflow::SingleFrameTooLarge
+    pub fn deframe_length_header(&mut self, data_frame: &[u8]) -> Result<Frame<N>, DeframeError> {
+        let header = self.length_header.ok_or(DeframeError::NotConfigured)?;
+        let header_len = header.size();
+        self.deframe_declared_len(data_frame, |buffered| {
+            if buffered.len() < header_len {
+                return Ok(None);
+            }
+            match header.decode(buffered) {
+                0 => Err(DeframeError::InvalidLength),
+                len => usize::try_from(len).ok().and_then(|len| len.checked_add(header_len)).map(Some).ok_or(DeframeError::InvalidLength),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LengthHeader;
+    use crate::{DeframeError, Deframer, Overflow};
+
+    #[test]
+    fn decodes_headers_of_either_byte_order() {
+        let mut deframer = Deframer::<8>::with_length_header(LengthHeader::U16Be);
+        let (data, len) = deframer.deframe_length_header(&[0x00, 0x02, 0x11]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (data_end, len_end) = deframer.deframe_length_header(&[0x22, 0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], []);
+        assert_eq!(data_end[0..len_end], [0x00, 0x02, 0x11, 0x22]);
+        assert_eq!(deframer.remainder(), [0x00]);
+
+        let mut deframer = Deframer::<8>::with_length_header(LengthHeader::U32Le);
+        let (data, len) = deframer.deframe_length_header(&[0x01, 0x00, 0x00, 0x00, 0x33]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x00, 0x00, 0x00, 0x33]);
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        let mut deframer = Deframer::<8>::with_length_header(LengthHeader::U8);
+        assert_eq!(deframer.deframe_length_header(&[0x00, 0x11]), Err(DeframeError::InvalidLength));
+        assert!(deframer.is_empty());
+
+        let mut deframer = Deframer::<8>::with_length_header(LengthHeader::U16Le);
+        let result = deframer.deframe_length_header(&[0x07, 0x00]);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+    }
+
+    #[test]
+    fn rejects_a_deframer_without_a_length_header() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.deframe_length_header(&[0x00, 0x01, 0x11]), Err(DeframeError::NotConfigured));
+        assert!(deframer.is_empty());
+    }
+}
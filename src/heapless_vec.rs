@@ -0,0 +1,40 @@
+//! Frames returned as `heapless::Vec`s, for projects built around the `heapless` containers
+
+use heapless::Vec;
+
+use crate::{DeframeError, Deframer, Element, FrameEnd, Overflow};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Like [`Deframer::next_frame`], but returns the frame as a `heapless::Vec` holding just its
+    /// valid elements. A `heapless::Vec` can be passed as `data_frame` directly, as it
+    /// dereferences to a slice.
+    pub fn next_frame_vec(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Vec<T, N>>, DeframeError> {
+        let len = match self.deframe_frame(data_frame, None, FrameEnd::Finish, get_frame_end)? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        let frame = Vec::from_slice(&self.frame[0..len]).map_err(|()| DeframeError::Overflow(Overflow::SingleFrameTooLarge))?;
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use heapless::Vec;
+
+    use crate::Deframer;
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn returns_frames_as_heapless_vecs() {
+        let mut deframer = Deframer::<4>::new();
+        let data: Vec<u8, 8> = Vec::from_slice(&[0x01, FRAME_END, 0x02]).unwrap();
+        let frame = deframer.next_frame_vec(&data, GET_FRAME_END).unwrap().unwrap();
+        assert_eq!(frame, [0x01, FRAME_END]);
+        assert_eq!(deframer.next_frame_vec(&[], GET_FRAME_END).unwrap(), None);
+    }
+}
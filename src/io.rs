@@ -0,0 +1,204 @@
+//! Reading frames from an [`embedded_io::Read`] source, such as a UART driver
+
+use embedded_io::{Error, ErrorKind, Read, ReadReady};
+
+use crate::{DeframeError, Deframer, Frame};
+
+/// The most bytes read from a reader at a time, which are buffered on the stack until deframed
+pub const READ_CHUNK_LEN: usize = 64;
+
+/// Reads whole frames from a reader, pulling in bytes as needed
+pub struct FrameReader<R, const N: usize, F = fn(&mut core::slice::Iter<u8>) -> Option<usize>> {
+    reader: R,
+    deframer: Deframer<N>,
+    get_frame_end: F,
+}
+
+/// An error from reading a frame
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReadFrameError<E> {
+    /// The reader failed
+    Read(E),
+    /// The data read couldn't be deframed
+    Deframe(DeframeError),
+    /// The reader reached the end of its data before a frame was complete
+    UnexpectedEof,
+}
+
+impl<R: Read, const N: usize, F: FnMut(&mut core::slice::Iter<u8>) -> Option<usize>> FrameReader<R, N, F> {
+    /// Wraps `reader`, splitting the data read from it into frames with `get_frame_end` as with
+    /// [`Deframer::deframe`]
+    pub fn new(reader: R, get_frame_end: F) -> Self {
+        Self::with_deframer(reader, Deframer::new(), get_frame_end)
+    }
+
+    /// Like [`FrameReader::new`], but deframes with an already configured `deframer`
+    pub fn with_deframer(reader: R, deframer: Deframer<N>, get_frame_end: F) -> Self {
+        Self {
+            reader,
+            deframer,
+            get_frame_end,
+        }
+    }
+
+    /// Returns the deframer, for example to inspect its remainder
+    pub fn deframer(&self) -> &Deframer<N> {
+        &self.deframer
+    }
+
+    /// Returns the wrapped reader, discarding any partial frame
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Blocks until a whole frame has been read, and returns it.
+    ///
+    /// Bytes are read in chunks of up to [`READ_CHUNK_LEN`] bytes that fit in the remainder. What
+    /// is read after the frame end stays buffered, and is returned by the following calls before
+    /// anything more is read. Reads that fail with [`ErrorKind::Interrupted`] are retried, any
+    /// other read error is returned and leaves the partial frame buffered.
+    pub fn read_frame(&mut self) -> Result<Frame<N>, ReadFrameError<R::Error>> {
+        if let Some(frame) = self.buffered_frame()? {
+            return Ok(frame);
+        }
+        loop {
+            if let Some(frame) = self.read_chunk()? {
+                return Ok(frame);
+            }
+        }
+    }
+
+    /// Returns a frame that was read by an earlier call, if any
+    fn buffered_frame(&mut self) -> Result<Option<Frame<N>>, ReadFrameError<R::Error>> {
+        self.deframer.next_frame(&[], &mut self.get_frame_end).map_err(ReadFrameError::Deframe)
+    }
+
+    /// Reads a chunk and deframes it, returning the first frame it completes
+    fn read_chunk(&mut self) -> Result<Option<Frame<N>>, ReadFrameError<R::Error>> {
+        let mut chunk = [0; READ_CHUNK_LEN];
+        let chunk = &mut chunk[0..read_len(&self.deframer)];
+        match self.reader.read(chunk) {
+            Ok(0) => Err(ReadFrameError::UnexpectedEof),
+            Ok(len) => self.deframer.next_frame(&chunk[0..len], &mut self.get_frame_end).map_err(ReadFrameError::Deframe),
+            Err(error) if error.kind() == ErrorKind::Interrupted => Ok(None),
+            Err(error) => Err(ReadFrameError::Read(error)),
+        }
+    }
+}
+
+impl<R: Read + ReadReady, const N: usize, F: FnMut(&mut core::slice::Iter<u8>) -> Option<usize>> FrameReader<R, N, F> {
+    /// Like [`FrameReader::read_frame`], but never blocks. Only the bytes that are ready to be
+    /// read are taken, and `Ok(None)` is returned if they don't complete a frame.
+    pub fn try_read_frame(&mut self) -> Result<Option<Frame<N>>, ReadFrameError<R::Error>> {
+        if let Some(frame) = self.buffered_frame()? {
+            return Ok(Some(frame));
+        }
+        while self.reader.read_ready().map_err(ReadFrameError::Read)? {
+            if let Some(frame) = self.read_chunk()? {
+                return Ok(Some(frame));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Returns how many bytes to read next into `deframer`, which is as many as fit in its remainder
+/// up to [`READ_CHUNK_LEN`]. At least one byte is read, so that a full remainder is reported as
+/// overflowing rather than never being read into.
+pub(crate) fn read_len<const N: usize>(deframer: &Deframer<N>) -> usize {
+    (deframer.remainder_capacity() - deframer.remainder_len()).clamp(1, READ_CHUNK_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use core::slice::Iter;
+
+    use embedded_io::{ErrorType, Read, ReadReady};
+
+    use super::{FrameReader, ReadFrameError};
+    use crate::{DeframeError, Overflow};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.rposition(|&x| x == FRAME_END);
+
+    #[test]
+    fn reads_one_frame_at_a_time() {
+        let data: &[u8] = &[0x01, FRAME_END, 0x02, 0x03, FRAME_END, 0x04];
+        let mut reader = FrameReader::<_, 8>::new(data, GET_FRAME_END);
+
+        assert_eq!(*reader.read_frame().unwrap(), [0x01, FRAME_END]);
+        assert_eq!(*reader.read_frame().unwrap(), [0x02, 0x03, FRAME_END]);
+
+        assert_eq!(reader.read_frame(), Err(ReadFrameError::UnexpectedEof));
+        assert_eq!(reader.deframer().remainder(), [0x04]);
+    }
+
+    /// A reader that counts the calls to [`Read::read`]
+    struct CountingReader<'a> {
+        data: &'a [u8],
+        reads: usize,
+    }
+
+    impl ErrorType for CountingReader<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for CountingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+            self.reads += 1;
+            self.data.read(buf)
+        }
+    }
+
+    #[test]
+    fn reads_in_chunks() {
+        let data = [0x01, 0x02, 0x03, FRAME_END, 0x04, FRAME_END, 0x05];
+        let mut reader = FrameReader::<_, 8>::new(CountingReader { data: &data, reads: 0 }, GET_FRAME_END);
+
+        assert_eq!(*reader.read_frame().unwrap(), [0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(*reader.read_frame().unwrap(), [0x04, FRAME_END]);
+        assert_eq!(reader.deframer().remainder(), [0x05]);
+        assert_eq!(reader.into_inner().reads, 1);
+    }
+
+    #[test]
+    fn reads_no_more_than_fits_in_the_remainder() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, FRAME_END];
+        let mut reader = FrameReader::<_, 4>::new(CountingReader { data: &data, reads: 0 }, GET_FRAME_END);
+        assert_eq!(reader.read_frame(), Err(ReadFrameError::Deframe(DeframeError::Overflow(Overflow::RemainderExhausted))));
+        // The whole remainder was filled by the first read, and the second overflowed it
+        assert_eq!(reader.into_inner().reads, 2);
+    }
+
+    /// A non-blocking reader whose received bytes are all ready to be read
+    struct Uart<'a> {
+        received: &'a [u8],
+    }
+
+    impl ErrorType for Uart<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for Uart<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+            self.received.read(buf)
+        }
+    }
+
+    impl ReadReady for Uart<'_> {
+        fn read_ready(&mut self) -> Result<bool, Infallible> {
+            Ok(!self.received.is_empty())
+        }
+    }
+
+    #[test]
+    fn only_reads_ready_bytes() {
+        let uart = Uart { received: &[0x01, FRAME_END, 0x02] };
+        let mut reader = FrameReader::<_, 8>::new(uart, GET_FRAME_END);
+
+        assert_eq!(*reader.try_read_frame().unwrap().unwrap(), [0x01, FRAME_END]);
+        assert_eq!(reader.try_read_frame(), Ok(None));
+        assert_eq!(reader.deframer().remainder(), [0x02]);
+    }
+}
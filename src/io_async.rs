@@ -0,0 +1,141 @@
+//! Reading frames from an [`embedded_io_async::Read`] source, such as an async UART driver
+
+use embedded_io_async::{Error, ErrorKind, Read};
+
+use crate::io::read_len;
+use crate::{Deframer, Frame, ReadFrameError, READ_CHUNK_LEN};
+
+/// Like [`crate::FrameReader`], but reads whole frames from an async reader
+pub struct AsyncFrameReader<R, const N: usize, F = fn(&mut core::slice::Iter<u8>) -> Option<usize>> {
+    reader: R,
+    deframer: Deframer<N>,
+    get_frame_end: F,
+}
+
+impl<R: Read, const N: usize, F: FnMut(&mut core::slice::Iter<u8>) -> Option<usize>> AsyncFrameReader<R, N, F> {
+    /// Wraps `reader`, splitting the data read from it into frames with `get_frame_end` as with
+    /// [`Deframer::deframe`]
+    pub fn new(reader: R, get_frame_end: F) -> Self {
+        Self::with_deframer(reader, Deframer::new(), get_frame_end)
+    }
+
+    /// Like [`AsyncFrameReader::new`], but deframes with an already configured `deframer`
+    pub fn with_deframer(reader: R, deframer: Deframer<N>, get_frame_end: F) -> Self {
+        Self {
+            reader,
+            deframer,
+            get_frame_end,
+        }
+    }
+
+    /// Returns the deframer, for example to inspect its remainder
+    pub fn deframer(&self) -> &Deframer<N> {
+        &self.deframer
+    }
+
+    /// Returns the wrapped reader, discarding any partial frame
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads until a whole frame has arrived, and returns it. The partial frame stays buffered
+    /// across awaits, so the future can be dropped and `read_frame` called again without losing
+    /// data that was already read.
+    ///
+    /// Bytes are read in chunks of up to [`READ_CHUNK_LEN`] bytes that fit in the remainder. What
+    /// is read after the frame end stays buffered, and is returned by the following calls before
+    /// anything more is read. Reads that fail with [`ErrorKind::Interrupted`] are retried, any
+    /// other read error is returned and leaves the partial frame buffered.
+    pub async fn read_frame(&mut self) -> Result<Frame<N>, ReadFrameError<R::Error>> {
+        if let Some(frame) = self.deframer.next_frame(&[], &mut self.get_frame_end).map_err(ReadFrameError::Deframe)? {
+            return Ok(frame);
+        }
+        loop {
+            let mut chunk = [0; READ_CHUNK_LEN];
+            let chunk = &mut chunk[0..read_len(&self.deframer)];
+            match self.reader.read(chunk).await {
+                Ok(0) => return Err(ReadFrameError::UnexpectedEof),
+                Ok(len) => {
+                    if let Some(frame) = self.deframer.next_frame(&chunk[0..len], &mut self.get_frame_end).map_err(ReadFrameError::Deframe)? {
+                        return Ok(frame);
+                    }
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(ReadFrameError::Read(error)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::slice::Iter;
+    use core::task::{Context, Poll, Waker};
+
+    use embedded_io_async::{ErrorType, Read};
+
+    use super::AsyncFrameReader;
+    use crate::{DeframeError, Overflow, ReadFrameError};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.rposition(|&x| x == FRAME_END);
+
+    /// Polls a future that never waits to completion
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the future is waiting"),
+        }
+    }
+
+    #[test]
+    fn reads_one_frame_per_await() {
+        let data: &[u8] = &[0x01, FRAME_END, 0x02, 0x03, FRAME_END, 0x04, 0x05, 0x06];
+        let mut reader = AsyncFrameReader::<_, 4>::new(data, GET_FRAME_END);
+
+        assert_eq!(*block_on(reader.read_frame()).unwrap(), [0x01, FRAME_END]);
+        assert_eq!(*block_on(reader.read_frame()).unwrap(), [0x02, 0x03, FRAME_END]);
+
+        assert_eq!(block_on(reader.read_frame()), Err(ReadFrameError::UnexpectedEof));
+        assert_eq!(reader.deframer().remainder(), [0x04, 0x05, 0x06]);
+    }
+
+    /// A reader that counts the calls to [`Read::read`]
+    struct CountingReader<'a> {
+        data: &'a [u8],
+        reads: usize,
+    }
+
+    impl ErrorType for CountingReader<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for CountingReader<'_> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+            self.reads += 1;
+            self.data.read(buf).await
+        }
+    }
+
+    #[test]
+    fn reads_in_chunks() {
+        let data = [0x01, 0x02, 0x03, FRAME_END, 0x04, FRAME_END, 0x05];
+        let mut reader = AsyncFrameReader::<_, 8>::new(CountingReader { data: &data, reads: 0 }, GET_FRAME_END);
+
+        assert_eq!(*block_on(reader.read_frame()).unwrap(), [0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(*block_on(reader.read_frame()).unwrap(), [0x04, FRAME_END]);
+        assert_eq!(reader.deframer().remainder(), [0x05]);
+        assert_eq!(reader.into_inner().reads, 1);
+    }
+
+    #[test]
+    fn surfaces_overflow() {
+        let data: &[u8] = &[0x01, 0x02, 0x03];
+        let mut reader = AsyncFrameReader::<_, 2>::new(data, GET_FRAME_END);
+        assert_eq!(block_on(reader.read_frame()), Err(ReadFrameError::Deframe(DeframeError::Overflow(Overflow::RemainderExhausted))));
+    }
+}
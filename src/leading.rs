@@ -0,0 +1,107 @@
+//! Framing where the delimiter leads each frame, such as a type byte, rather than ending it
+
+use crate::{first_frame_end, DeframeError, Deframer, Element, Frame, FrameEnd, Overflow};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Deframes data where each frame starts with a marker found by `get_frame_start`, splitting
+    /// frames before each marker rather than after it. The marker is kept as the first element of
+    /// the frame it starts, so a frame is only complete once the marker of the next one arrives.
+    /// Any data before the first marker is returned as a frame of its own.
+    ///
+    /// The frame is returned as is, without checksum verification, transformation or stripping.
+    /// As with [`Deframer::deframe`], the remainder and `data_frame` are discarded on error.
+    pub fn deframe_leading(&mut self, data_frame: &[T], get_frame_start: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Frame<N, T>, DeframeError> {
+        Ok(self.next_frame_leading(data_frame, get_frame_start)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_leading`], but returns `Ok(None)` while no complete frame is
+    /// available
+    pub fn next_frame_leading(&mut self, data_frame: &[T], mut get_frame_start: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Frame<N, T>>, DeframeError> {
+        // A marker at the very start of the data ends the buffered frame, unless a frame that was
+        // buffered before it is still to be returned
+        let data_starts_frame = self.remainder_length > 0 && matches!(first_frame_end(data_frame, self.forward_search, &mut get_frame_start), Ok(Some(0)));
+        if data_starts_frame && !self.has_frame(|iter| after_first(iter, &mut get_frame_start)) {
+            return self.take_leading_frame(data_frame);
+        }
+
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Raw(1), |iter| after_first(iter, &mut get_frame_start))?;
+        Ok(len.map(|len| Frame::new(data, self.count_frame(len))))
+    }
+
+    /// Returns the whole remainder as a frame, ended by the marker at the start of `data_frame`,
+    /// and buffers `data_frame` in its place
+    fn take_leading_frame(&mut self, data_frame: &[T]) -> Result<Option<Frame<N, T>>, DeframeError> {
+        self.count_bytes(data_frame.len());
+        self.count_call();
+        self.consumed = 0;
+        let len = self.remainder_length;
+        if len > N {
+            return Err(self.discard(len + data_frame.len(), DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        }
+        if data_frame.len() > BUF {
+            return Err(self.discard(len + data_frame.len(), DeframeError::Overflow(Overflow::RemainderExhausted)));
+        }
+
+        // [marker...frame] is replaced by [marker...data]
+        let mut data: [T; N] = [T::ZERO; N];
+        data[0..len].copy_from_slice(&self.remainder[0..len]);
+        self.clear_remainder();
+        self.remainder[0..data_frame.len()].copy_from_slice(data_frame);
+        self.remainder_length = data_frame.len();
+        Ok(Some(Frame::new(data, self.count_frame(len))))
+    }
+}
+
+/// Finds the position just before the first marker after the one leading the frame, which is
+/// where the frame ends
+fn after_first<T>(iter: &mut core::slice::Iter<T>, mut get_frame_start: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Option<usize> {
+    iter.next();
+    get_frame_start(iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use crate::{DeframeError, Deframer, Overflow};
+
+    const START: u8 = 0x7E;
+    const GET_FRAME_START: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == START);
+
+    #[test]
+    fn splits_frames_before_each_marker() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_leading(&[START, 0x01, 0x02, START, 0x03], GET_FRAME_START).unwrap().into_parts();
+        assert_eq!(data[0..len], [START, 0x01, 0x02]);
+        assert_eq!(deframer.remainder(), [START, 0x03]);
+
+        // The marker arrives at the start of the next data frame
+        let (data, len) = deframer.deframe_leading(&[START, 0x04], GET_FRAME_START).unwrap().into_parts();
+        assert_eq!(data[0..len], [START, 0x03]);
+        assert_eq!(deframer.remainder(), [START, 0x04]);
+        assert_eq!(deframer.next_frame_leading(&[0x05], GET_FRAME_START).unwrap(), None);
+    }
+
+    #[test]
+    fn drains_buffered_frames_in_order() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_leading(&[0x01, START, 0x02, START], GET_FRAME_START).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01]);
+        let (data, len) = deframer.deframe_leading(&[START, 0x03], GET_FRAME_START).unwrap().into_parts();
+        assert_eq!(data[0..len], [START, 0x02]);
+        let (data, len) = deframer.deframe_leading(&[], GET_FRAME_START).unwrap().into_parts();
+        assert_eq!(data[0..len], [START]);
+        assert_eq!(deframer.remainder(), [START, 0x03]);
+    }
+
+    #[test]
+    fn overflows_when_the_frame_is_too_large() {
+        let mut deframer = Deframer::<2, u8, 4>::new();
+        deframer.deframe_leading(&[START, 0x01, 0x02], GET_FRAME_START).unwrap();
+        let result = deframer.deframe_leading(&[START], GET_FRAME_START);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 4);
+    }
+}
@@ -0,0 +1,152 @@
+//! Length-prefixed framing, where each frame starts with a header declaring its total length
+//! rather than ending with a delimiter
+
+use crate::{consume_remainder, DeframeError, Deframer, Element, Frame, Overflow};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Deframes data where the length of each frame is declared up front. `get_frame_len` is
+    /// given the buffered elements of the current frame so far and returns its expected total
+    /// length, or `None` if the header hasn't been fully received yet. Once that many elements
+    /// have arrived the whole frame, header included, is returned.
+    ///
+    /// Returns `Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge))` if the declared
+    /// length exceeds `N`, or `Err(DeframeError::InvalidLength)` if it is zero. As with
+    /// [`Deframer::deframe`], the remainder and `data_frame` are discarded on error.
+    pub fn deframe_length_prefixed(&mut self, data_frame: &[T], mut get_frame_len: impl FnMut(&[T]) -> Option<usize>) -> Result<Frame<N, T>, DeframeError> {
+        self.deframe_declared_len(data_frame, |buffered| Ok(get_frame_len(buffered)))
+    }
+
+    /// Like [`Deframer::deframe_length_prefixed`], but `get_frame_len` may also reject the header
+    pub(crate) fn deframe_declared_len(&mut self, data_frame: &[T], get_frame_len: impl FnMut(&[T]) -> Result<Option<usize>, DeframeError>) -> Result<Frame<N, T>, DeframeError> {
+        self.count_bytes(data_frame.len());
+        self.count_call();
+        let buffered = self.remainder_length + data_frame.len();
+        self.length_prefixed_frame(data_frame, get_frame_len)
+            .map_err(|error| self.discard(buffered, error))
+    }
+
+    fn length_prefixed_frame(&mut self, data_frame: &[T], mut get_frame_len: impl FnMut(&[T]) -> Result<Option<usize>, DeframeError>) -> Result<Frame<N, T>, DeframeError> {
+        // Top up the remainder from the data until the header can be decoded
+        let mut consumed = 0;
+        let frame_len = loop {
+            if let Some(frame_len) = get_frame_len(&self.remainder[0..self.remainder_length])? {
+                break Some(frame_len);
+            }
+            if consumed == data_frame.len() {
+                break None;
+            }
+            if self.remainder_length == BUF {
+                return Err(DeframeError::Overflow(Overflow::RemainderExhausted));
+            }
+            self.remainder[self.remainder_length] = data_frame[consumed];
+            self.remainder_length += 1;
+            consumed += 1;
+        };
+
+        let frame_len = match frame_len {
+            Some(frame_len) => frame_len,
+            None => return Ok(Frame::default()),
+        };
+
+        if frame_len == 0 {
+            return Err(DeframeError::InvalidLength);
+        }
+        if frame_len > N {
+            return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+        }
+
+        let rest = &data_frame[consumed..data_frame.len()];
+
+        // Not enough data has arrived yet, buffer all of it for the next call
+        if frame_len > self.remainder_length + rest.len() {
+            let end_pos = self.remainder_length + rest.len();
+            if end_pos > BUF {
+                return Err(DeframeError::Overflow(Overflow::RemainderExhausted));
+            }
+            self.remainder[self.remainder_length..end_pos].copy_from_slice(rest);
+            self.remainder_length = end_pos;
+            return Ok(Frame::default());
+        }
+
+        let mut data: [T; N] = [T::ZERO; N];
+        let rest = if frame_len <= self.remainder_length {
+            // The whole frame is already buffered, shift whatever follows it to the front
+            data[0..frame_len].copy_from_slice(&self.remainder[0..frame_len]);
+            consume_remainder(&mut self.remainder, &mut self.remainder_length, frame_len);
+            rest
+        } else {
+            // [remainder...rest of the frame]
+            let needed = frame_len - self.remainder_length;
+            data[0..self.remainder_length].copy_from_slice(&self.remainder[0..self.remainder_length]);
+            data[self.remainder_length..frame_len].copy_from_slice(&rest[0..needed]);
+            self.clear_remainder();
+            &rest[needed..rest.len()]
+        };
+
+        let end_pos = self.remainder_length + rest.len();
+        if end_pos > BUF {
+            return Err(DeframeError::Overflow(Overflow::RemainderExhausted));
+        }
+        self.remainder[self.remainder_length..end_pos].copy_from_slice(rest);
+        self.remainder_length = end_pos;
+
+        Ok(Frame::new(data, self.count_frame(frame_len)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, Deframer, Overflow};
+
+    /// For these tests, the first byte of each frame is the length of its payload
+    const GET_FRAME_LEN: fn(buffered: &[u8]) -> Option<usize> = |buffered| buffered.first().map(|&len| len as usize + 1);
+
+    #[test]
+    fn deframes_length_prefixed_frames() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_length_prefixed(&[0x02, 0x11, 0x22, 0x01], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, 0x11, 0x22]);
+        assert_eq!(deframer.remainder(), [0x01]);
+
+        let (data, len) = deframer.deframe_length_prefixed(&[0x33], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x33]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn accumulates_until_the_frame_is_complete() {
+        let mut deframer = Deframer::<8>::new();
+        let (_data, len) = deframer.deframe_length_prefixed(&[], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (_data, len) = deframer.deframe_length_prefixed(&[0x03], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (_data, len) = deframer.deframe_length_prefixed(&[0x11, 0x22], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(len, 0);
+
+        let (data, len) = deframer.deframe_length_prefixed(&[0x33, 0x00], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x03, 0x11, 0x22, 0x33]);
+        assert_eq!(deframer.remainder(), [0x00]);
+
+        // A zero length payload is already complete in the remainder
+        let (data, len) = deframer.deframe_length_prefixed(&[0x01], GET_FRAME_LEN).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x00]);
+        assert_eq!(deframer.remainder(), [0x01]);
+    }
+
+    #[test]
+    fn overflows_when_the_declared_length_exceeds_capacity() {
+        let mut deframer = Deframer::<4>::new();
+        let result = deframer.deframe_length_prefixed(&[0x04, 0x11], GET_FRAME_LEN);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 2);
+    }
+
+    #[test]
+    fn rejects_a_zero_frame_length() {
+        let mut deframer = Deframer::<4>::new();
+        let result = deframer.deframe_length_prefixed(&[0x00, 0x11], |buffered| buffered.first().map(|&len| len as usize));
+        assert_eq!(result, Err(DeframeError::InvalidLength));
+        assert!(deframer.is_empty());
+    }
+}
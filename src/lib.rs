@@ -1,100 +1,1295 @@
 //! # deframe
 //! A data deframer for embedded and no_std applications
+//!
+//! The crate contains no unsafe code, which is enforced with `#![forbid(unsafe_code)]`.
 
 #![no_std]
+#![forbid(unsafe_code)]
 #![deny(warnings)]
 #![allow(dead_code)]
 
-pub struct Deframer<const N: usize> {
-    remainder: [u8; N],
+#[cfg(any(test, feature = "alloc"))]
+extern crate alloc;
+
+pub use builder::DeframerBuilder;
+pub use context::ErrorContext;
+pub use frame::Frame;
+pub use header::LengthHeader;
+pub use multi::MultiDeframer;
+pub use policy::DelimiterPolicy;
+pub use queue::{DeframerQueue, QueueError};
+pub use search::{find_frame_end, find_sequence_end, frames_in, search_frame_end};
+pub use sink::FrameSink;
+pub use slice::SliceDeframer;
+pub use stats::DeframerStats;
+pub use transform::Transform;
+#[cfg(feature = "embedded-io")]
+pub use io::{FrameReader, ReadFrameError, READ_CHUNK_LEN};
+#[cfg(feature = "embedded-io-async")]
+pub use io_async::AsyncFrameReader;
+#[cfg(feature = "alloc")]
+pub use vec::DeframerVec;
+#[cfg(feature = "zeroize")]
+pub use wipe::WipingDeframer;
+use checksum::Checksum;
+use policy::CarriedEnd;
+
+mod boundary;
+mod bounded;
+mod builder;
+mod checksum;
+mod cobs;
+mod context;
+mod crlf;
+mod doubled;
+mod fixed;
+mod frame;
+mod header;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
+#[cfg(feature = "embedded-io")]
+mod io;
+#[cfg(feature = "embedded-io-async")]
+mod io_async;
+mod leading;
+mod length_prefixed;
+mod masked;
+mod multi;
+#[cfg(feature = "serde")]
+mod persist;
+mod policy;
+mod queue;
+mod search;
+mod sequence;
+mod sink;
+mod slice;
+mod slip;
+mod stale;
+mod stats;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod transform;
+#[cfg(feature = "alloc")]
+mod vec;
+#[cfg(feature = "zeroize")]
+mod wipe;
+
+/// An element of the deframed stream, such as a byte or a sample
+pub trait Element: Copy + PartialEq {
+    /// The value used to initialise buffers
+    const ZERO: Self;
+}
+
+macro_rules! impl_element {
+    ($($t:ty),*) => {
+        $(impl Element for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_element!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The maximum number of delimiters a deframer can be configured with
+pub const MAX_DELIMITERS: usize = 4;
+
+/// The maximum length of a delimiter sequence, see [`Deframer::with_delimiter_seq`]
+pub const MAX_DELIMITER_SEQ_LEN: usize = 8;
+
+/// Splits a stream of elements into frames of up to `N` elements, buffering up to `BUF` elements
+/// of an incomplete frame between calls. `BUF` defaults to `N`, but may be larger to tolerate
+/// bursts of data ahead of a frame end, or smaller to save memory.
+///
+/// A deframer is `Copy`, so its state can be saved before a speculative call and restored after.
+/// This rules out a `Drop` implementation, so a deframer can't wipe its buffers as it goes out of
+/// scope. With the `zeroize` feature, hold it in a `WipingDeframer` to have them wiped on drop
+/// instead, at no cost to deframers that aren't wrapped.
+///
+/// A capacity `N` of zero could never return a frame, so constructing such a deframer fails to
+/// compile:
+///
+/// ```compile_fail
+/// let deframer = deframe::Deframer::<0>::new();
+/// ```
+#[derive(Clone, Copy)]
+pub struct Deframer<const N: usize, T = u8, const BUF: usize = N> {
+    remainder: [T; BUF],
     remainder_length: usize,
+    delimiters: [T; MAX_DELIMITERS],
+    delimiter_count: usize,
+    delimiter_seq: [T; MAX_DELIMITER_SEQ_LEN],
+    delimiter_seq_len: usize,
+    after_delimiter: bool,
+    after_cr: bool,
+    frame: [T; N],
+    dropped_bytes: usize,
+    resyncing: bool,
+    delimiter_policy: DelimiterPolicy,
+    carried_end: CarriedEnd<T>,
+    checksum: Option<Checksum<T>>,
+    max_frame_len: Option<usize>,
+    min_frame_len: Option<usize>,
+    forward_search: bool,
+    consumed: usize,
+    stats: DeframerStats,
+    frame_counter: usize,
+    fixed_len: usize,
+    transform: Option<Transform<T>>,
+    escape: Option<T>,
+    length_header: Option<LengthHeader>,
+    calls_since_frame: usize,
+    skip_empty: bool,
+    strict_full_buffer: bool,
+    last_error: Option<ErrorContext>,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DeframeError {
-    Overflow,
+    /// Data didn't fit in the deframer's buffers
+    Overflow(Overflow),
+    /// A frame was not validly encoded for the framing in use
+    InvalidEncoding,
+    /// The checksum carried by a frame didn't match the one computed over its payload
+    ChecksumMismatch { expected: u16, computed: u16 },
+    /// A frame was longer than the maximum frame length, even though it fit in the buffer
+    FrameTooLong,
+    /// `get_frame_end` returned a position outside of the data it was given
+    InvalidFrameEnd,
+    /// A length-prefixed frame declared a length of zero, or one too large to represent
+    InvalidLength,
+    /// A frame was too short to carry the checksum it was expected to end with
+    MalformedFrame,
+    /// The deframer wasn't constructed for the framing used, such as calling
+    /// [`Deframer::deframe_fixed`] on one not made with [`Deframer::with_fixed_len`]
+    NotConfigured,
+}
+
+/// The cause of a [`DeframeError::Overflow`]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Overflow {
+    /// A single frame, up to its frame end, was larger than the buffer. Only that frame is lost,
+    /// so skipping it is enough to recover.
+    SingleFrameTooLarge,
+    /// Data without a frame end filled the remainder, which suggests a frame that is never
+    /// terminated and a link that needs to be resynchronised
+    RemainderExhausted,
 }
 
-impl<const N: usize> Deframer<N> {
-    pub fn new() -> Self {
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Creates a deframer whose delimiter for [`Deframer::deframe_delimited`] is zero
+    pub const fn new() -> Self {
+        Self::with_delimiter(T::ZERO)
+    }
+
+    /// Creates a deframer that ends frames on `delimiter` when using [`Deframer::deframe_delimited`]
+    pub const fn with_delimiter(delimiter: T) -> Self {
+        // Built up rather than borrowing `[delimiter]`, which a `const fn` can't do before 1.83
+        let mut deframer = Self::with_delimiters(&[]);
+        deframer.delimiters[0] = delimiter;
+        deframer.delimiter_count = 1;
+        deframer
+    }
+
+    /// Creates a deframer that ends frames on any of `delimiters` when using
+    /// [`Deframer::deframe_delimited`].
+    ///
+    /// A run of consecutive delimiters, such as a CR LF line ending, counts as a single frame
+    /// boundary: the frame ends at the first delimiter of the run and the rest of the run is
+    /// discarded, even if it arrives in a later data frame. This means that no empty frames are
+    /// emitted between consecutive delimiters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_DELIMITERS`] delimiters are given.
+    pub const fn with_delimiters(delimiters: &[T]) -> Self {
+        const { assert!(N > 0, "deframer capacity N of zero") };
         Self {
-            remainder: [0; N],
+            remainder: [T::ZERO; BUF],
             remainder_length: 0,
+            delimiters: store_delimiters(delimiters),
+            delimiter_count: delimiters.len(),
+            delimiter_seq: [T::ZERO; MAX_DELIMITER_SEQ_LEN],
+            delimiter_seq_len: 0,
+            after_delimiter: false,
+            after_cr: false,
+            frame: [T::ZERO; N],
+            dropped_bytes: 0,
+            resyncing: false,
+            delimiter_policy: DelimiterPolicy::Include,
+            carried_end: CarriedEnd::new(),
+            checksum: None,
+            max_frame_len: None,
+            min_frame_len: None,
+            forward_search: false,
+            consumed: 0,
+            stats: DeframerStats::new(),
+            frame_counter: 0,
+            fixed_len: 0,
+            transform: None,
+            escape: None,
+            length_header: None,
+            calls_since_frame: 0,
+            skip_empty: false,
+            strict_full_buffer: false,
+            last_error: None,
         }
     }
 
-    pub fn deframe(&mut self, data_frame: &[u8], get_frame_end: fn(iter: &mut core::slice::Iter<u8>) -> Option<usize>) -> Result<([u8; N], usize), DeframeError> {
-        // check if the last read had some dangling/remainding bytes after the last linebreak
-        let with_remainder = self.remainder_length != 0;
+    /// Creates a deframer that ends frames on `delimiter`, with stripping and the maximum frame
+    /// length configured as with [`Deframer::set_strip_delimiter`] and
+    /// [`Deframer::set_max_frame_len`]. Being `const`, it can initialise a `static` deframer.
+    pub const fn with_config(delimiter: T, strip_delimiter: bool, max_frame_len: usize) -> Self {
+        let mut deframer = Self::with_delimiter(delimiter);
+        deframer.delimiter_policy = if strip_delimiter { DelimiterPolicy::Strip } else { DelimiterPolicy::Include };
+        deframer.max_frame_len = Some(max_frame_len);
+        deframer
+    }
 
-        if !with_remainder {
-            let frame_end_result =  get_frame_end(&mut data_frame.iter());
-            let frame_end_pos = if frame_end_result.is_some() { 
-                frame_end_result.unwrap() + 1
-            } else {
-                // If no frame end is found, then all the data is reserve data
-                0
-            };
-        
-            self.remainder_length = data_frame.len() - frame_end_pos;
-            self.remainder[0..self.remainder_length]
-                .copy_from_slice(&data_frame[frame_end_pos..data_frame.len()]);
-
-            let mut data: [u8; N] = [0; N];
-            if frame_end_pos > N {
-                return Err(DeframeError::Overflow);
-            }
-            data[0..frame_end_pos].copy_from_slice(&data_frame[0..frame_end_pos]);
-            return Ok((data, frame_end_pos));
-        }
-
-        // Keep finding the next valid position until our data is within the buffer size
-        let mut iter = data_frame.iter();
-        let mut last_valid_pos: usize = N;
-        while last_valid_pos + self.remainder_length > N {
-            let frame_end_result = get_frame_end(&mut iter);
-            if frame_end_result.is_some() {
-                last_valid_pos = frame_end_result.unwrap() + 1;
-            } else {
-                last_valid_pos = 0;
+    /// Creates a deframer that resumes with `remainder` already buffered, such as one saved from
+    /// [`Deframer::remainder`] before a low power cycle. Any frame ends in it are found by the
+    /// next call, as for frames left in the remainder by an earlier call.
+    ///
+    /// Returns `Err(DeframeError::Overflow(Overflow::RemainderExhausted))` if `remainder` is
+    /// longer than the remainder capacity `BUF`, which is the only state that is restored. Any
+    /// shorter remainder is valid, as its elements are deframed like any other data, so a
+    /// corrupted one can't make a later call panic: frames in it that are too long for `N` are
+    /// returned as errors and dropped like any others. Detecting corrupted elements themselves
+    /// needs a check over the saved remainder, such as a CRC stored alongside it.
+    pub fn from_parts(remainder: &[T]) -> Result<Self, DeframeError> {
+        let mut deframer = Self::new();
+        append(&mut deframer.remainder, &mut deframer.remainder_length, remainder, Overflow::RemainderExhausted)?;
+        Ok(deframer)
+    }
+
+    /// Discards any buffered remainder, so the next call to [`Deframer::deframe`] behaves as if
+    /// the deframer was freshly constructed
+    pub fn reset(&mut self) {
+        self.remainder = [T::ZERO; BUF];
+        self.remainder_length = 0;
+        self.after_delimiter = false;
+        self.after_cr = false;
+        self.resyncing = false;
+        self.calls_since_frame = 0;
+        self.last_error = None;
+        self.carried_end = CarriedEnd::new();
+    }
+
+    /// Prepares for deframing the rest of the stream in a different mode, or with a different
+    /// `get_frame_end`, such as after a text handshake upgrades to a binary protocol. The
+    /// remainder is kept, as it holds the start of the data in the new framing, and is searched
+    /// with the new framing on the next call. What is forgotten is the state carried over from the
+    /// old framing, such as a CR LF or run of delimiters split across data frames, which would
+    /// otherwise skip the start of the new data.
+    ///
+    /// The old framing should have emitted its last frame before switching, for example by
+    /// draining with an empty data frame. To discard the remainder instead, call
+    /// [`Deframer::flush`] or [`Deframer::reset`] before switching.
+    pub fn switch_mode(&mut self) {
+        self.after_delimiter = false;
+        self.after_cr = false;
+        self.carried_end = CarriedEnd::new();
+    }
+
+    /// Returns the buffered remainder as a final, unterminated frame and removes it, such as when
+    /// the stream closes. No frame end is expected, so the frame is returned as is, without
+    /// checksum verification or stripping.
+    ///
+    /// At most `N` elements are returned, so if `BUF` is larger than `N` the rest stay buffered
+    /// for the next call.
+    pub fn flush(&mut self) -> Frame<N, T> {
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = self.remainder_length.min(N);
+        data[0..len].copy_from_slice(&self.remainder[0..len]);
+        consume_remainder(&mut self.remainder, &mut self.remainder_length, len);
+        if len > 0 {
+            self.count_frame(len);
+        }
+        Frame::new(data, len)
+    }
+
+    /// Flushes the remainder as with [`Deframer::flush`] and then resets the deframer as with
+    /// [`Deframer::reset`], such as when a connection is re-established. Anything past the first
+    /// `N` elements of the remainder is counted in [`Deframer::dropped_bytes`].
+    pub fn clear_and_return(&mut self) -> Frame<N, T> {
+        let frame = self.flush();
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(self.remainder_length);
+        self.reset();
+        frame
+    }
+
+    /// Returns the bytes that have been buffered but not yet emitted as part of a frame
+    pub fn remainder(&self) -> &[T] {
+        &self.remainder[0..self.remainder_length]
+    }
+
+    /// Returns the number of buffered bytes waiting for a frame end
+    pub fn remainder_len(&self) -> usize {
+        self.remainder_length
+    }
+
+    /// Returns the capacity `N` of the deframer's frame buffer, which is the longest frame it can
+    /// return
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the capacity `BUF` of the deframer's remainder, which is the most data it can
+    /// buffer while waiting for a frame end
+    pub const fn remainder_capacity(&self) -> usize {
+        BUF
+    }
+
+    /// Returns true if the remainder already holds a frame end found by `get_frame_end`, so that
+    /// a complete frame is available without waiting for more data. The deframer isn't modified.
+    ///
+    /// A position outside of the remainder isn't a frame end, so returns false.
+    pub fn has_frame(&self, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> bool {
+        matches!(first_frame_end(self.remainder(), self.forward_search, get_frame_end), Ok(Some(_)))
+    }
+
+    /// Returns the number of complete frames in the remainder, each ending at a frame end found by
+    /// `get_frame_end`, which can be taken without waiting for more data. The deframer isn't
+    /// modified. Empty or short frames aren't counted if they are being skipped.
+    pub fn count_frames(&self, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> usize {
+        let mut remainder = self.remainder();
+        let mut count = 0;
+        while let Ok(Some(pos)) = first_frame_end(remainder, self.forward_search, &mut get_frame_end) {
+            if pos >= self.min_payload_len() {
+                count += 1;
+            }
+            remainder = &remainder[pos + 1..remainder.len()];
+        }
+        count
+    }
+
+    /// Returns the next complete frame in the remainder without removing it, so that it can be
+    /// inspected before deciding whether to take it with [`Deframer::deframe`]. The frame is
+    /// returned as it is buffered, without checksum verification, transformation or stripping.
+    ///
+    /// Returns `None` if the remainder holds no frame end found by `get_frame_end`, or if the
+    /// frame is longer than `N`.
+    pub fn peek_frame(&self, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Option<Frame<N, T>> {
+        let mut remainder = self.remainder();
+        while let Ok(Some(pos)) = first_frame_end(remainder, self.forward_search, &mut get_frame_end) {
+            if pos >= self.min_payload_len() {
+                break;
+            }
+            remainder = &remainder[pos + 1..remainder.len()];
+        }
+        let len = match first_frame_end(remainder, self.forward_search, get_frame_end) {
+            Ok(Some(pos)) if pos < N => pos + 1,
+            _ => return None,
+        };
+        let mut data: [T; N] = [T::ZERO; N];
+        data[0..len].copy_from_slice(&remainder[0..len]);
+        Some(Frame::new(data, len))
+    }
+
+    /// Returns true if no bytes are buffered
+    pub fn is_empty(&self) -> bool {
+        self.remainder_length == 0
+    }
+
+    /// Zeroes the remainder buffer past the buffered elements, so that no stale data is left in
+    /// it. Elements are already zeroed as they leave the remainder, which is checked in debug
+    /// builds, so this only guarantees it regardless.
+    pub fn compact(&mut self) {
+        self.remainder[self.remainder_length..BUF].fill(T::ZERO);
+    }
+
+    /// Sets whether the element ending each frame is excluded from the returned frame. It is
+    /// still consumed from the stream either way, and only the frame end itself is removed.
+    /// Frames include their frame end by default.
+    pub fn set_strip_delimiter(&mut self, strip_delimiter: bool) {
+        self.set_delimiter_policy(if strip_delimiter { DelimiterPolicy::Strip } else { DelimiterPolicy::Include });
+    }
+
+    /// Sets whether frames made up of nothing but their frame end, such as between consecutive
+    /// delimiters, are skipped rather than returned as empty frames. Their frame ends are still
+    /// consumed from the stream. This applies to frame ends that are a single element, so not to
+    /// [`Deframer::deframe_sequence`]. Empty frames are returned by default.
+    pub fn set_skip_empty_frames(&mut self, skip_empty: bool) {
+        self.skip_empty = skip_empty;
+    }
+
+    /// Sets the shortest frame that is returned, not counting its frame end, or `None` to return
+    /// frames of any length. A shorter frame, such as noise that happened to contain a frame end,
+    /// is skipped along with its frame end as if it were never received, so it is neither returned
+    /// nor counted in [`Deframer::dropped_bytes`]. As with [`Deframer::set_skip_empty_frames`],
+    /// this applies to frame ends that are a single element.
+    pub fn set_min_frame_len(&mut self, min_frame_len: Option<usize>) {
+        self.min_frame_len = min_frame_len;
+    }
+
+    /// Sets the longest frame that is accepted, not counting its frame end, or `None` to accept any
+    /// frame that fits in the buffer.
+    ///
+    /// A longer frame is dropped with [`DeframeError::FrameTooLong`]. Buffered data already longer
+    /// than the maximum is discarded with the same error as soon as it arrives, rather than when
+    /// the buffer overflows, so a sender that never ends its frame is cut off sooner. With
+    /// [`Deframer::deframe_boundary`] the width of a boundary is only known once its frame is
+    /// complete, so buffered data is left to the buffer size instead.
+    pub fn set_max_frame_len(&mut self, max_frame_len: Option<usize>) {
+        self.max_frame_len = max_frame_len;
+    }
+
+    /// Sets whether `get_frame_end` is trusted to return the first frame end in its iterator, as
+    /// [`Iterator::position`] and [`search_frame_end`] do. The data before a frame end found is
+    /// then not searched again for an earlier one, so each frame end is found with a single call.
+    ///
+    /// This is off by default, as a callback that returns a later frame end, such as
+    /// [`Iterator::rposition`], would merge the frames before it into one.
+    pub fn set_forward_search(&mut self, forward_search: bool) {
+        self.forward_search = forward_search;
+    }
+
+    /// Sets whether a remainder that fills the whole buffer without a frame end is an error as
+    /// soon as it happens, rather than once the next call overflows. Such a frame is probably
+    /// truncated, as it is already `BUF` elements long before its frame end, so it is discarded
+    /// with `Err(DeframeError::Overflow(Overflow::RemainderExhausted))`. This applies to the
+    /// delimiter based methods, and is off by default.
+    pub fn set_strict_full_buffer(&mut self, strict_full_buffer: bool) {
+        self.strict_full_buffer = strict_full_buffer;
+    }
+
+    /// Discards the remainder and skips all incoming data up to and including the next frame end,
+    /// so that a corrupted partial frame is never emitted and deframing realigns on the frame
+    /// that follows it. The skipped elements are counted in [`Deframer::dropped_bytes`].
+    ///
+    /// Length-prefixed deframing has no frame end to realign on, so this only applies to the
+    /// delimiter based methods.
+    pub fn resync(&mut self) {
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(self.remainder_length);
+        self.clear_remainder();
+        self.resyncing = true;
+    }
+
+    /// Returns the total number of elements discarded because of errors since construction,
+    /// wrapping on overflow
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    /// Returns how many elements of the last data frame were used up, either by being returned as
+    /// part of the frame or by being discarded, rather than held in the remainder. The data frame
+    /// is always used up from the front, so whatever follows this count is now in the remainder.
+    ///
+    /// This is updated by the methods that return at most one frame per call, such as
+    /// [`Deframer::deframe`] and [`Deframer::deframe_delimited`]. If no frame is returned the
+    /// count is zero, unless elements were skipped or discarded.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Prepends any remainder from previous calls to `data_frame` and returns the data up to the
+    /// first frame end in `data_frame`, buffering what follows it for the next call. If no frame
+    /// end is found, all of the data is buffered and an empty frame is returned.
+    ///
+    /// When a data frame holds several frames, the later ones are left in the remainder and
+    /// returned in order by the following calls, before any frame in their data. Calling with an
+    /// empty data frame drains them one at a time. The remainder is always searched along with the
+    /// data, so a frame end that is already buffered, such as one that an earlier `get_frame_end`
+    /// didn't match, still ends a frame.
+    ///
+    /// `get_frame_end` is expected to return the position of the first frame end in the iterator,
+    /// as [`Iterator::position`] does. A callback that returns a later one, such as
+    /// [`Iterator::rposition`], is called again on the data before it until the earliest frame
+    /// end is found, so that frames are always emitted in order and never merged, but searches
+    /// the data more than once. See [`search_frame_end`] for a ready made forward search, and
+    /// [`Deframer::set_forward_search`] to search the data only once.
+    ///
+    /// `get_frame_end` may therefore be called more than once per frame and on the same elements,
+    /// also because buffered elements are searched again on each call along with the new data. It
+    /// should only search, rather than keep state such as a count or a running checksum, as that
+    /// state would take in elements more than once.
+    ///
+    /// An empty `data_frame`, such as from a read that returned nothing, leaves the remainder
+    /// untouched unless it already holds a frame end, in which case that frame is returned.
+    ///
+    /// If an error is returned, the remainder and all of `data_frame` are discarded and counted in
+    /// [`Deframer::dropped_bytes`], so the next call starts afresh. The exception is a frame too
+    /// large to be returned, which is dropped only up to its frame end, so that the frames after it
+    /// are still returned by the following calls.
+    ///
+    /// Each call zeroes a new `[T; N]` buffer for the frame, which is measurable for a large `N`.
+    /// [`Deframer::next_frame`] only does so once a frame is complete, and
+    /// [`Deframer::deframe_into`] and [`Deframer::deframe_slice`] skip it entirely, as they only
+    /// write the elements of the frame into a buffer that is reused between calls.
+    pub fn deframe(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Frame<N, T>, DeframeError> {
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Finish, get_frame_end)?;
+        Ok(Frame::new(data, len.unwrap_or(0)))
+    }
+
+    /// Like [`Deframer::deframe`], but returns `Ok(None)` while the data is still being buffered
+    /// and no complete frame is available, so an empty frame is never confused with no frame.
+    /// The frame is assembled in a buffer owned by the deframer and only copied out once complete.
+    pub fn next_frame(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Frame<N, T>>, DeframeError> {
+        let len = self.deframe_frame(data_frame, None, FrameEnd::Finish, get_frame_end)?;
+        Ok(len.map(|len| Frame::from_slice(&self.frame[0..len])))
+    }
+
+    /// Like [`Deframer::deframe`], but writes the frame into the start of `out` and returns its
+    /// length, leaving the rest of `out` untouched. Frames are still limited to the capacity `N`,
+    /// and a frame that doesn't fit in `out` is an [`Overflow::SingleFrameTooLarge`] error.
+    ///
+    /// Nothing is zeroed, so whatever `out` held past the returned length, such as the end of a
+    /// longer frame written by an earlier call, is still there and must not be read as part of
+    /// the frame.
+    pub fn deframe_into(&mut self, data_frame: &[T], out: &mut [T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<usize, DeframeError> {
+        let capacity = out.len().min(N);
+        let len = self.deframe_frame(data_frame, Some(&mut out[0..capacity]), FrameEnd::Finish, get_frame_end)?;
+        Ok(len.unwrap_or(0))
+    }
+
+    /// Like [`Deframer::deframe`], but assembles the frame in a buffer owned by the deframer and
+    /// returns a slice of its valid region, avoiding zeroing and copying a full `[T; N]` array
+    pub fn deframe_slice(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<&[T], DeframeError> {
+        let len = self.deframe_frame(data_frame, None, FrameEnd::Finish, get_frame_end)?;
+        Ok(&self.frame[0..len.unwrap_or(0)])
+    }
+
+    /// Like [`Deframer::deframe`], but also returns the tail of `data_frame` that wasn't used up
+    /// and is now held in the remainder, as counted by [`Deframer::consumed`]
+    pub fn deframe_with_tail<'a>(&mut self, data_frame: &'a [T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<(Frame<N, T>, &'a [T]), DeframeError> {
+        let frame = self.deframe(data_frame, get_frame_end)?;
+        Ok((frame, &data_frame[self.consumed..data_frame.len()]))
+    }
+
+    /// Like [`Deframer::deframe`], but the data is given in `parts` that are treated as one
+    /// contiguous data frame, such as the two halves of a DMA ring buffer that wraps around. The
+    /// parts are read in place, so there is no need to copy them into a linear buffer first.
+    pub fn deframe_chained(&mut self, parts: &[&[T]], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Frame<N, T>, DeframeError> {
+        let length = parts.iter().map(|part| part.len()).sum();
+        self.count_bytes(length);
+        self.count_call();
+
+        // Resynchronise across the parts in order, so the data starts after the first frame end
+        let mut first = 0;
+        let mut head: &[T] = &[];
+        let mut skip = Ok(());
+        while first < parts.len() {
+            match self.skip_to_frame_end(parts[first], &mut get_frame_end) {
+                Ok(data) => head = data,
+                Err(error) => skip = Err(error),
+            }
+            first += 1;
+            if !self.resyncing || skip.is_err() {
                 break;
             }
         }
+        if skip.is_ok() {
+            match self.skip_short_frames(head, &mut get_frame_end) {
+                Ok(data) => head = data,
+                Err(error) => skip = Err(error),
+            }
+        }
+        let tail = &parts[first..parts.len()];
+        let unskipped = head.len() + tail.iter().map(|part| part.len()).sum::<usize>();
+
+        let buffered = self.remainder_length + unskipped;
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = match skip.and_then(|()| self.take_pending_frame(&[head], Some(&mut data), &mut get_frame_end)) {
+            Ok(None) => deframe_parts_into(&mut self.remainder, &mut self.remainder_length, head, tail, &mut data, self.forward_search, get_frame_end),
+            Ok(Some(len)) => tail.iter().try_for_each(|part| append(&mut self.remainder, &mut self.remainder_length, part, Overflow::RemainderExhausted)).map(|()| Some(len)),
+            Err(error) => Err(error),
+        };
+        let len = self.complete_frame(len, Some(&mut data), FrameEnd::Finish, length, length - unskipped, buffered)?;
+        Ok(Frame::new(data, len.unwrap_or(0)))
+    }
+
+    /// Deframes `data_frame` using the delimiters the deframer was constructed with, rather than
+    /// a `get_frame_end` function
+    pub fn deframe_delimited(&mut self, data_frame: &[T]) -> Result<Frame<N, T>, DeframeError> {
+        Ok(self.next_frame_delimited(data_frame)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_delimited`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a lone delimiter stripped down to an empty frame can be told apart
+    pub fn next_frame_delimited(&mut self, mut data_frame: &[T]) -> Result<Option<Frame<N, T>>, DeframeError> {
+        let delimiters = self.delimiters;
+        let delimiters = &delimiters[0..self.delimiter_count];
+        let mut data: [T; N] = [T::ZERO; N];
+
+        let mut skipped = 0;
+        if self.after_delimiter {
+            // Skip the rest of the run of delimiters that ended the previous frame
+            skipped = data_frame.iter().take_while(|x| delimiters.contains(x)).count();
+            data_frame = &data_frame[skipped..data_frame.len()];
+            self.count_bytes(skipped);
+            if data_frame.is_empty() {
+                self.consumed = skipped;
+                return Ok(None);
+            }
+            self.after_delimiter = false;
+        }
+
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Finish, |iter| iter.position(|x| delimiters.contains(x)));
+        self.consumed += skipped;
+        let len = len?;
+
+        if len.is_some() {
+            // The rest of the run is at the front of the remainder, and may continue in the next
+            // data frame if it takes up all of the remainder
+            let run = self.remainder[0..self.remainder_length].iter().take_while(|x| delimiters.contains(x)).count();
+            consume_remainder(&mut self.remainder, &mut self.remainder_length, run);
+            self.consumed += run;
+            self.after_delimiter = self.remainder_length == 0;
+        }
+        Ok(len.map(|len| Frame::new(data, len)))
+    }
+
+    /// Deframes `data_frame` into `out`, or the deframer's own frame buffer if `None`, returning
+    /// the frame length if a frame end was found. How the frame ends, and whether it is then
+    /// finished as configured by validating its checksum and stripping its frame end, is given by
+    /// `end`.
+    fn deframe_frame(&mut self, data_frame: &[T], out: Option<&mut [T]>, end: FrameEnd, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+        let room = BUF - self.remainder_length;
+        self.try_deframe_frame(data_frame, out, end, get_frame_end).map_err(|error| self.record_error(error, data_frame.len(), room))
+    }
+
+    fn try_deframe_frame(&mut self, data_frame: &[T], mut out: Option<&mut [T]>, end: FrameEnd, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+        let escape = if matches!(end, FrameEnd::Finish) { self.escape } else { None };
+        let mut get_frame_end = transform::skip_escaped(escape, self.remainder(), data_frame, self.forward_search, get_frame_end);
+        let length = data_frame.len();
+        self.count_bytes(length);
+        self.count_call();
+        let data_frame = match self.skip_to_frame_end(data_frame, &mut get_frame_end).and_then(|data_frame| self.skip_short_frames(data_frame, &mut get_frame_end)) {
+            Ok(data_frame) => data_frame,
+            Err(error) => return self.complete_frame(Err(error), out, end, length, length, self.remainder_length + length),
+        };
+        let buffered = self.remainder_length + data_frame.len();
+        let len = match self.take_pending_frame(&[data_frame], out.as_deref_mut(), &mut get_frame_end) {
+            Ok(None) if length == 0 => {
+                // An empty read, such as one that timed out, leaves the remainder untouched
+                self.consumed = 0;
+                return Ok(None);
+            }
+            Ok(None) => assemble_frame(&mut self.remainder, &mut self.remainder_length, data_frame, out.as_deref_mut().unwrap_or(&mut self.frame), self.forward_search, &mut get_frame_end),
+            len => len,
+        };
+        if len == Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)) {
+            return Err(self.drop_oversized_frame(data_frame, length, get_frame_end));
+        }
+        self.complete_frame(len, out, end, length, length - data_frame.len(), buffered)
+    }
+
+    /// Drops a frame too large to be returned, up to and including its frame end, and buffers
+    /// what follows it so that the frames after it are still returned. Everything is discarded if
+    /// what follows doesn't fit in the remainder.
+    fn drop_oversized_frame(&mut self, data_frame: &[T], length: usize, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> DeframeError {
+        let error = DeframeError::Overflow(Overflow::SingleFrameTooLarge);
+        // The frame ends in the remainder if it holds a frame end, otherwise in the data
+        let (from_remainder, rest) = match first_frame_end(self.remainder(), self.forward_search, &mut get_frame_end) {
+            Ok(Some(pos)) => (pos + 1, data_frame),
+            _ => match first_frame_end(data_frame, self.forward_search, &mut get_frame_end) {
+                Ok(Some(pos)) => (self.remainder_length, &data_frame[pos + 1..data_frame.len()]),
+                _ => return self.discard(self.remainder_length + data_frame.len(), error),
+            },
+        };
+        self.consumed = length - rest.len();
+        if self.remainder_length - from_remainder + rest.len() > BUF {
+            return self.discard(self.remainder_length + data_frame.len(), error);
+        }
+
+        self.count_error(error);
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(from_remainder + data_frame.len() - rest.len());
+        consume_remainder(&mut self.remainder, &mut self.remainder_length, from_remainder);
+        self.remainder[self.remainder_length..self.remainder_length + rest.len()].copy_from_slice(rest);
+        self.remainder_length += rest.len();
+        error
+    }
+
+    /// Completes deframing `length` elements of data, of which `skipped` were skipped while
+    /// resynchronising, into `out` given the `len` of the raw frame. On error the `buffered`
+    /// elements are discarded, otherwise the frame is checked and finished as given by `end`.
+    fn complete_frame(&mut self, len: Result<Option<usize>, DeframeError>, out: Option<&mut [T]>, end: FrameEnd, length: usize, skipped: usize, buffered: usize) -> Result<Option<usize>, DeframeError> {
+        let len = match len {
+            Ok(len) => len,
+            Err(error) => {
+                self.consumed = length;
+                return Err(self.discard(buffered, error));
+            }
+        };
+        // The old remainder is at the front of any frame found, so at most the data is left in the
+        // remainder, unless the frame was already buffered and all of the data was appended
+        self.consumed = match len {
+            Some(_) => length - self.remainder_length.min(length - skipped),
+            None => skipped,
+        };
+
+        // Check the frame, or the remainder if no frame end was found, against the maximum length.
+        // The remainder may hold all but the last element of the frame end.
+        if let (Some(max_frame_len), Some(end_len)) = (self.max_frame_len, end.len()) {
+            match len {
+                Some(len) if len - end_len > max_frame_len => {
+                    self.dropped_bytes = self.dropped_bytes.wrapping_add(len);
+                    return Err(DeframeError::FrameTooLong);
+                }
+                None if self.remainder_length > max_frame_len + end_len.saturating_sub(1) => {
+                    self.consumed = length;
+                    self.dropped_bytes = self.dropped_bytes.wrapping_add(self.remainder_length);
+                    self.clear_remainder();
+                    return Err(DeframeError::FrameTooLong);
+                }
+                _ => {}
+            }
+        }
+        if len.is_none() && self.strict_full_buffer && self.remainder_length == BUF {
+            self.consumed = length;
+            return Err(self.discard(BUF, DeframeError::Overflow(Overflow::RemainderExhausted)));
+        }
+        match len {
+            Some(len) if matches!(end, FrameEnd::Finish) => match finish_frame(out.unwrap_or(&mut self.frame), len, 1, self.checksum, self.transform, self.delimiter_policy, &mut self.carried_end) {
+                Ok(len) => Ok(Some(self.count_frame(len))),
+                Err(error) => {
+                    self.dropped_bytes = self.dropped_bytes.wrapping_add(len);
+                    Err(error)
+                }
+            },
+            len => Ok(len),
+        }
+    }
+
+    /// If the remainder already holds a frame end, such as one left after a frame end by an
+    /// earlier call or one that an earlier `get_frame_end` didn't match, takes the frame ending at
+    /// the earliest one into `out`, or the deframer's own frame buffer if `None`, and appends the
+    /// data `parts` to the remainder. Returns `None`, leaving everything untouched, if the
+    /// remainder holds no frame end.
+    fn take_pending_frame(&mut self, parts: &[&[T]], out: Option<&mut [T]>, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+        if self.remainder_length == 0 {
+            return Ok(None);
+        }
+        let len = take_buffered_frame(&mut self.remainder, &mut self.remainder_length, out.unwrap_or(&mut self.frame), self.forward_search, get_frame_end)?;
+        if len.is_none() {
+            return Ok(None);
+        }
+        for part in parts {
+            append(&mut self.remainder, &mut self.remainder_length, part, Overflow::RemainderExhausted)?;
+        }
+        Ok(len)
+    }
+
+    /// Returns the length below which frames are skipped, not counting their frame end
+    fn min_payload_len(&self) -> usize {
+        self.min_frame_len.unwrap_or(0).max(self.skip_empty as usize)
+    }
+
+    /// When skipping empty or short frames, skips any that are at the front of the remainder, or
+    /// that start in the remainder and end in `data_frame`, returning the data that follows them
+    fn skip_short_frames<'a>(&mut self, mut data_frame: &'a [T], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<&'a [T], DeframeError> {
+        let min_len = self.min_payload_len();
+        if min_len == 0 {
+            return Ok(data_frame);
+        }
+        loop {
+            match first_frame_end(self.remainder(), self.forward_search, &mut get_frame_end)? {
+                Some(pos) if pos < min_len => consume_remainder(&mut self.remainder, &mut self.remainder_length, pos + 1),
+                Some(_) => break,
+                // The frame at the front of the remainder ends in the data, if at all
+                None => match first_frame_end(data_frame, self.forward_search, &mut get_frame_end)? {
+                    Some(pos) if self.remainder_length + pos < min_len => {
+                        self.clear_remainder();
+                        data_frame = &data_frame[pos + 1..data_frame.len()];
+                    }
+                    _ => break,
+                },
+            }
+        }
+        Ok(data_frame)
+    }
+
+    /// While resynchronising, skips `data_frame` up to and including its first frame end,
+    /// returning the data that follows it
+    fn skip_to_frame_end<'a>(&mut self, data_frame: &'a [T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<&'a [T], DeframeError> {
+        if !self.resyncing {
+            return Ok(data_frame);
+        }
+
+        let skipped = match first_frame_end(data_frame, self.forward_search, get_frame_end)? {
+            Some(pos) => {
+                self.resyncing = false;
+                pos + 1
+            }
+            None => data_frame.len(),
+        };
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(skipped);
+        Ok(&data_frame[skipped..data_frame.len()])
+    }
+
+    /// Buffers a single element, returning the frame it completes if it is a frame end. This is
+    /// equivalent to calling [`Deframer::next_frame`] with a one element data frame.
+    pub fn push(&mut self, element: T, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Frame<N, T>>, DeframeError> {
+        self.next_frame(&[element], get_frame_end)
+    }
+
+    /// Returns an iterator that pulls elements from `elements` and yields each frame as soon as
+    /// it is complete, with the same buffering and overflow behaviour as [`Deframer::next_frame`].
+    /// The iterator ends when `elements` does, leaving any partial frame in the remainder.
+    pub fn frames<I: Iterator<Item = T>, F: FnMut(&mut core::slice::Iter<T>) -> Option<usize>>(&mut self, elements: I, get_frame_end: F) -> Frames<'_, I, N, T, BUF, F> {
+        Frames {
+            deframer: self,
+            elements,
+            get_frame_end,
+        }
+    }
+
+    /// Discards the remainder after an error, counting the `dropped` elements
+    fn discard(&mut self, dropped: usize, error: DeframeError) -> DeframeError {
+        self.count_error(error);
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(dropped);
+        // A failed call may have left data past the remainder, so the whole buffer is cleared
+        self.remainder.fill(T::ZERO);
+        self.remainder_length = 0;
+        error
+    }
+
+    /// Empties the remainder, zeroing the elements it held
+    pub(crate) fn clear_remainder(&mut self) {
+        self.remainder[0..self.remainder_length].fill(T::ZERO);
+        self.remainder_length = 0;
+    }
+
+    /// Returns an iterator over every complete frame in `data_frame`, each one ending at the
+    /// earliest frame end reported by `get_frame_end`. The first frame is prefixed with any
+    /// remainder from previous calls, and once no further frame end is found the trailing bytes
+    /// are pushed to the remainder. The iterator must be driven to completion for those trailing
+    /// bytes to be buffered.
+    ///
+    /// A frame larger than `N` yields `Err(DeframeError::Overflow(_))` and is dropped, after which
+    /// iteration continues with the following frame. Dropped frames are counted in
+    /// [`Deframer::dropped_bytes`].
+    pub fn deframe_all<'d, 'a, F: FnMut(&mut core::slice::Iter<T>) -> Option<usize>>(&'d mut self, data_frame: &'a [T], get_frame_end: F) -> DeframeAll<'d, 'a, N, T, BUF, F> {
+        self.count_bytes(data_frame.len());
+        self.count_call();
+        DeframeAll {
+            deframer: self,
+            data_frame,
+            get_frame_end,
+        }
+    }
+
+    /// Calls `f` with every complete frame in `data_frame`, as found by
+    /// [`Deframer::deframe_all`]. Each frame is assembled in a buffer owned by the deframer, so no
+    /// `[T; N]` array is returned per frame.
+    ///
+    /// Frames that fail, for example by overflowing, are dropped and deframing continues with the
+    /// following frame. The first such error is returned once all of `data_frame` is processed.
+    pub fn deframe_each(&mut self, data_frame: &[T], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, mut f: impl FnMut(&[T])) -> Result<(), DeframeError> {
+        self.count_bytes(data_frame.len());
+        self.count_call();
+        let buffered = self.remainder_length + data_frame.len();
+        let mut data_frame = self.skip_to_frame_end(data_frame, &mut get_frame_end).map_err(|error| self.discard(buffered, error))?;
+        let mut result = Ok(());
+        while let Some(len) = self.take_frame(&mut data_frame, None, &mut get_frame_end) {
+            match len {
+                Ok(len) => f(&self.frame[0..len]),
+                Err(error) => result = result.and(Err(error)),
+            }
+        }
+        result
+    }
+
+    /// Writes every complete frame in `data_frame` into `out` in order, as found by
+    /// [`Deframer::deframe_all`], and returns how many were written. Once `out` is full the rest
+    /// of the data is buffered, so the frames that didn't fit are returned by the next call.
+    ///
+    /// Frames that fail are dropped and deframing continues with the following frame, as with
+    /// [`Deframer::deframe_each`]. The first such error is returned if no frame was written,
+    /// otherwise it is only recorded in [`Deframer::last_error`] so that the written frames
+    /// aren't lost.
+    pub fn deframe_batch(&mut self, data_frame: &[T], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, out: &mut [Frame<N, T>]) -> Result<usize, DeframeError> {
+        let length = data_frame.len();
+        self.count_bytes(length);
+        self.count_call();
+        let room = BUF - self.remainder_length;
+        let buffered = self.remainder_length + length;
+        let mut data_frame = self.skip_to_frame_end(data_frame, &mut get_frame_end).map_err(|error| self.discard(buffered, error))?;
+
+        let mut count = 0;
+        let mut first_error = None;
+        while count < out.len() {
+            match self.take_frame(&mut data_frame, None, &mut get_frame_end) {
+                Some(Ok(len)) => {
+                    out[count] = Frame::from_slice(&self.frame[0..len]);
+                    count += 1;
+                }
+                Some(Err(error)) => {
+                    self.consumed = length - data_frame.len();
+                    first_error = first_error.or(Some(self.record_error(error, length, room)));
+                }
+                None => break,
+            }
+        }
+        // Whatever is left, including any frames that didn't fit in `out`, is buffered
+        if !data_frame.is_empty() {
+            let rest = self.remainder_length + data_frame.len();
+            if let Err(error) = append(&mut self.remainder, &mut self.remainder_length, data_frame, Overflow::RemainderExhausted) {
+                self.consumed = length;
+                let error = self.discard(rest, error);
+                first_error = first_error.or(Some(self.record_error(error, length, room)));
+            }
+        }
+        match first_error {
+            Some(error) if count == 0 => Err(error),
+            _ => Ok(count),
+        }
+    }
 
-        // No frame break was found, in this case all the current data must be pushed to the
-        // remainder (for the next deframe call) and no data returned to the user
-        if last_valid_pos == 0 {
-            if data_frame.len() + self.remainder_length > N {
-                return Err(DeframeError::Overflow);
+    /// Assembles the first complete frame of `data_frame` into `out`, or the deframer's own frame
+    /// buffer if `None`, and advances `data_frame` past it. Once no complete frame is left, the
+    /// rest of the data is pushed to the remainder and `None` is returned.
+    fn take_frame(&mut self, data_frame: &mut &[T], mut out: Option<&mut [T]>, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Option<Result<usize, DeframeError>> {
+        let mut get_frame_end = transform::skip_escaped(self.escape, self.remainder(), data_frame, self.forward_search, get_frame_end);
+        match self.skip_short_frames(data_frame, &mut get_frame_end) {
+            Ok(data) => *data_frame = data,
+            Err(error) => {
+                let dropped = self.remainder_length + core::mem::take(data_frame).len();
+                return Some(Err(self.discard(dropped, error)));
             }
-            self.remainder[self.remainder_length..data_frame.len() + self.remainder_length].copy_from_slice(&data_frame[0..data_frame.len()]);
-            self.remainder_length = data_frame.len() + self.remainder_length;
-            return Ok(([0; N], 0));
         }
 
-        // If there is remainding line data from the previous reader, prepend it
-        let mut appended: [u8; N] = [0; N];
-        appended[0..self.remainder_length]
-            .copy_from_slice(&self.remainder[0..self.remainder_length]);
+        // A frame end left in the remainder by an earlier call comes before any in the data
+        match self.take_pending_frame(&[], out.as_deref_mut(), &mut get_frame_end) {
+            Ok(Some(len)) if self.max_frame_len.is_some_and(|max| len - 1 > max) => {
+                self.dropped_bytes = self.dropped_bytes.wrapping_add(len);
+                return Some(Err(DeframeError::FrameTooLong));
+            }
+            Ok(Some(len)) => return Some(self.finish_taken_frame(out, len)),
+            Ok(None) => {}
+            Err(error) => return Some(Err(self.discard(self.remainder_length, error))),
+        }
+
+        let remainder_length = self.remainder_length;
+
+        let frame_end_pos = match first_frame_end(data_frame, self.forward_search, get_frame_end) {
+            Ok(Some(pos)) => pos + 1,
+            Ok(None) => {
+                // No more frames, the rest of the data is remainder for the next call
+                let rest = core::mem::take(data_frame);
+                let end_pos = remainder_length + rest.len();
+                if self.max_frame_len.is_some_and(|max| end_pos > max) {
+                    return Some(Err(self.discard(end_pos, DeframeError::FrameTooLong)));
+                }
+                if end_pos > BUF {
+                    return Some(Err(self.discard(end_pos, DeframeError::Overflow(Overflow::RemainderExhausted))));
+                }
+                self.remainder[remainder_length..end_pos].copy_from_slice(rest);
+                self.remainder_length = end_pos;
+                return None;
+            }
+            Err(error) => {
+                let dropped = remainder_length + core::mem::take(data_frame).len();
+                return Some(Err(self.discard(dropped, error)));
+            }
+        };
 
-        let end_pos = self.remainder_length + last_valid_pos;
+        let (frame, rest) = data_frame.split_at(frame_end_pos);
+        *data_frame = rest;
 
+        let end_pos = remainder_length + frame_end_pos;
+        if self.max_frame_len.is_some_and(|max| end_pos - 1 > max) {
+            return Some(Err(self.discard(end_pos, DeframeError::FrameTooLong)));
+        }
         if end_pos > N {
-            return Err(DeframeError::Overflow);
+            return Some(Err(self.discard(end_pos, DeframeError::Overflow(Overflow::SingleFrameTooLarge))));
         }
 
-        // [remainder...trimmed_data]
-        appended[self.remainder_length..end_pos].copy_from_slice(&data_frame[0..last_valid_pos]);
+        // [remainder...frame]
+        let frame_out = out.as_deref_mut().unwrap_or(&mut self.frame);
+        frame_out[0..remainder_length].copy_from_slice(&self.remainder[0..remainder_length]);
+        frame_out[remainder_length..end_pos].copy_from_slice(frame);
+        self.clear_remainder();
+        Some(self.finish_taken_frame(out, end_pos))
+    }
 
-        self.remainder_length = data_frame.len() - last_valid_pos;
-        
-        if self.remainder_length > N {
-            return Err(DeframeError::Overflow);
+    /// Finishes a raw frame of length `len` in `out`, or the deframer's own frame buffer if
+    /// `None`, counting it if it is valid and dropping it otherwise
+    fn finish_taken_frame(&mut self, out: Option<&mut [T]>, len: usize) -> Result<usize, DeframeError> {
+        match finish_frame(out.unwrap_or(&mut self.frame), len, 1, self.checksum, self.transform, self.delimiter_policy, &mut self.carried_end) {
+            Ok(frame_len) => Ok(self.count_frame(frame_len)),
+            Err(error) => {
+                self.dropped_bytes = self.dropped_bytes.wrapping_add(len);
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<const N: usize, T: core::fmt::Debug, const BUF: usize> core::fmt::Debug for Deframer<N, T, BUF> {
+    /// Shows only the valid part of the remainder, rather than the whole buffer
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Deframer")
+            .field("remainder_length", &self.remainder_length)
+            .field("remainder", &&self.remainder[0..self.remainder_length])
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize, T: PartialEq, const BUF: usize> PartialEq for Deframer<N, T, BUF> {
+    /// Compares only the valid part of the remainder, as whatever is past it is stale. The
+    /// configuration and counters aren't compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.remainder[0..self.remainder_length] == other.remainder[0..other.remainder_length]
+    }
+}
+
+impl<const N: usize, T: Eq, const BUF: usize> Eq for Deframer<N, T, BUF> {}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize, T: defmt::Format, const BUF: usize> defmt::Format for Deframer<N, T, BUF> {
+    /// Shows only the valid part of the remainder, rather than the whole buffer
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Deframer {{ remainder_length: {=usize}, remainder: {} }}", self.remainder_length, &self.remainder[0..self.remainder_length]);
+    }
+}
+
+impl<const N: usize, T: Element, const BUF: usize> Default for Deframer<N, T, BUF> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Deframes `data_frame` against the buffered `remainder`, writing the assembled frame into `out`
+/// and returning its length, or `None` if no frame end was found. The frame ends at the earliest
+/// frame end in `data_frame`, and everything after it becomes the new remainder.
+fn assemble_frame<T: Element>(remainder: &mut [T], remainder_length: &mut usize, data_frame: &[T], out: &mut [T], forward: bool, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+    let frame_end_pos = match first_frame_end(data_frame, forward, get_frame_end)? {
+        Some(pos) => pos + 1,
+        None => {
+            // No frame end was found, in this case all the current data must be pushed to the
+            // remainder (for the next deframe call) and no data returned to the user
+            append(remainder, remainder_length, data_frame, Overflow::RemainderExhausted)?;
+            return Ok(None);
+        }
+    };
+
+    // Check both the frame and what follows it fit before touching the remainder, a frame
+    // ending exactly at the capacity is still valid
+    let end_pos = *remainder_length + frame_end_pos;
+    if end_pos > out.len() {
+        return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+    }
+    let rest = &data_frame[frame_end_pos..data_frame.len()];
+    if rest.len() > remainder.len() {
+        return Err(DeframeError::Overflow(Overflow::RemainderExhausted));
+    }
+
+    // [remainder...data up to the frame end]
+    out[0..*remainder_length].copy_from_slice(&remainder[0..*remainder_length]);
+    out[*remainder_length..end_pos].copy_from_slice(&data_frame[0..frame_end_pos]);
+
+    // Whatever follows the frame end is the remainder for the next call
+    remainder[0..rest.len()].copy_from_slice(rest);
+    if rest.len() < *remainder_length {
+        remainder[rest.len()..*remainder_length].fill(T::ZERO);
+    }
+    *remainder_length = rest.len();
+    Ok(Some(end_pos))
+}
+
+/// Moves the frame ending at the earliest frame end already in `remainder` into `out`, shifting
+/// whatever follows it to the front, and returns its length or `None` if there is no frame end
+fn take_buffered_frame<T: Element>(remainder: &mut [T], remainder_length: &mut usize, out: &mut [T], forward: bool, get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+    let end_pos = match first_frame_end(&remainder[0..*remainder_length], forward, get_frame_end)? {
+        Some(pos) => pos + 1,
+        None => return Ok(None),
+    };
+    if end_pos > out.len() {
+        return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+    }
+    out[0..end_pos].copy_from_slice(&remainder[0..end_pos]);
+    consume_remainder(remainder, remainder_length, end_pos);
+    Ok(Some(end_pos))
+}
+
+/// Like [`assemble_frame`], but the data is `head` followed by each of the `tail` parts. The frame
+/// ends at the earliest frame end in the first part that has one.
+fn deframe_parts_into<T: Element>(remainder: &mut [T], remainder_length: &mut usize, head: &[T], tail: &[&[T]], out: &mut [T], forward: bool, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+    let count = tail.len() + 1;
+    let part = |i: usize| if i == 0 { head } else { tail[i - 1] };
+
+    let mut frame_end = None;
+    for i in 0..count {
+        if let Some(pos) = first_frame_end(part(i), forward, &mut get_frame_end)? {
+            frame_end = Some((i, pos + 1));
+            break;
+        }
+    }
+    let (end_part, end_pos) = match frame_end {
+        Some(frame_end) => frame_end,
+        None => {
+            // No frame end was found, so all the data is pushed to the remainder
+            for i in 0..count {
+                append(remainder, remainder_length, part(i), Overflow::RemainderExhausted)?;
+            }
+            return Ok(None);
+        }
+    };
+
+    // [remainder...parts up to the frame end]
+    let mut len = *remainder_length;
+    out[0..len].copy_from_slice(&remainder[0..len]);
+    for i in 0..end_part {
+        append(out, &mut len, part(i), Overflow::SingleFrameTooLarge)?;
+    }
+    append(out, &mut len, &part(end_part)[0..end_pos], Overflow::SingleFrameTooLarge)?;
+
+    // Whatever follows the frame end is the remainder for the next call
+    let old_length = core::mem::take(remainder_length);
+    let rest = part(end_part);
+    append(remainder, remainder_length, &rest[end_pos..rest.len()], Overflow::RemainderExhausted)?;
+    for i in end_part + 1..count {
+        append(remainder, remainder_length, part(i), Overflow::RemainderExhausted)?;
+    }
+    if *remainder_length < old_length {
+        remainder[*remainder_length..old_length].fill(T::ZERO);
+    }
+    Ok(Some(len))
+}
+
+/// Removes the first `count` of the `remainder_length` elements of `remainder`, shifting the rest
+/// to the front and zeroing the elements that this vacates
+fn consume_remainder<T: Element>(remainder: &mut [T], remainder_length: &mut usize, count: usize) {
+    assert!(count <= *remainder_length, "consumed past the remainder");
+    remainder.copy_within(count..*remainder_length, 0);
+    remainder[*remainder_length - count..*remainder_length].fill(T::ZERO);
+    *remainder_length -= count;
+    debug_assert!(remainder[*remainder_length..remainder.len()].iter().all(|&x| x == T::ZERO), "stale data past the remainder");
+}
+
+/// Appends `data` to the first `length` elements of `buffer`, failing with `overflow` if it
+/// doesn't fit
+fn append<T: Element>(buffer: &mut [T], length: &mut usize, data: &[T], overflow: Overflow) -> Result<(), DeframeError> {
+    let end = *length + data.len();
+    if end > buffer.len() {
+        return Err(DeframeError::Overflow(overflow));
+    }
+    buffer[*length..end].copy_from_slice(data);
+    *length = end;
+    Ok(())
+}
+
+/// Copies up to [`MAX_DELIMITERS`] `delimiters` into the fixed size array a deframer stores them in
+///
+/// # Panics
+///
+/// Panics if more than [`MAX_DELIMITERS`] delimiters are given.
+const fn store_delimiters<T: Element>(delimiters: &[T]) -> [T; MAX_DELIMITERS] {
+    assert!(delimiters.len() <= MAX_DELIMITERS, "too many delimiters");
+    let mut stored = [T::ZERO; MAX_DELIMITERS];
+    let mut i = 0;
+    while i < delimiters.len() {
+        stored[i] = delimiters[i];
+        i += 1;
+    }
+    stored
+}
+
+/// How a frame found by [`Deframer::deframe_frame`] ends
+#[derive(Clone, Copy)]
+enum FrameEnd {
+    /// A single element, after which the frame is finished as configured
+    Finish,
+    /// The given number of elements, with the frame returned raw for the caller to finish
+    Raw(usize),
+    /// Elements that are only known once the caller finishes the raw frame, so the caller checks
+    /// the frame length
+    RawUnknown,
+}
+
+impl FrameEnd {
+    /// Returns the number of elements ending the frame, which aren't counted against the maximum
+    /// frame length, if known
+    fn len(self) -> Option<usize> {
+        match self {
+            FrameEnd::Finish => Some(1),
+            FrameEnd::Raw(len) => Some(len),
+            FrameEnd::RawUnknown => None,
+        }
+    }
+}
+
+/// Finishes a raw frame of length `len` in `out`, whose frame end is `end_len` elements long, by
+/// transforming its payload, validating and removing its checksum, then placing its frame end as
+/// configured by `policy`, returning the final frame length
+fn finish_frame<T: Element>(out: &mut [T], mut len: usize, end_len: usize, checksum: Option<Checksum<T>>, transform: Option<Transform<T>>, policy: DelimiterPolicy, carried_end: &mut CarriedEnd<T>) -> Result<usize, DeframeError> {
+    if let Some(transform) = transform {
+        len = transform::transform_frame(out, len, end_len, transform)?;
+    }
+    if let Some(checksum) = checksum {
+        // Move the frame end down over the checksum, [payload...frame end]
+        let payload_len = checksum.verify(&out[0..len - end_len])?;
+        out.copy_within(len - end_len..len, payload_len);
+        out[payload_len + end_len..len].fill(T::ZERO);
+        len = payload_len + end_len;
+    }
+    policy::place_frame_end(out, len, end_len, policy, carried_end)
+}
+
+/// Clears the `end_len` long frame end from a frame of length `len` in `out`, returning the
+/// stripped length
+fn strip_frame_end<T: Element>(out: &mut [T], len: usize, end_len: usize) -> usize {
+    out[len - end_len..len].fill(T::ZERO);
+    len - end_len
+}
+
+/// Finds the earliest frame end within `data`, regardless of whether `get_frame_end` searches
+/// from the front or the back of the iterator, by searching the data before each frame end found
+/// again. If `forward` is set, `get_frame_end` is trusted to return the first frame end, so it is
+/// only called once. A position outside of `data` is an [`DeframeError::InvalidFrameEnd`] error
+/// rather than being trusted.
+fn first_frame_end<T>(data: &[T], forward: bool, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+    let mut end = match get_frame_end(&mut data.iter()) {
+        Some(end) if end < data.len() => end,
+        Some(_) => return Err(DeframeError::InvalidFrameEnd),
+        None => return Ok(None),
+    };
+    if !forward {
+        while let Some(earlier) = get_frame_end(&mut data[0..end].iter()).filter(|&pos| pos < end) {
+            end = earlier;
+        }
+    }
+    Ok(Some(end))
+}
+
+/// Iterator over the complete frames of a single data frame, see [`Deframer::deframe_all`]
+pub struct DeframeAll<'d, 'a, const N: usize, T, const BUF: usize, F> {
+    deframer: &'d mut Deframer<N, T, BUF>,
+    data_frame: &'a [T],
+    get_frame_end: F,
+}
+
+impl<'d, 'a, const N: usize, T: Element, const BUF: usize, F: FnMut(&mut core::slice::Iter<T>) -> Option<usize>> Iterator for DeframeAll<'d, 'a, N, T, BUF, F> {
+    type Item = Result<Frame<N, T>, DeframeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.deframer.take_frame(&mut self.data_frame, None, &mut self.get_frame_end)?;
+        Some(len.map(|len| Frame::from_slice(&self.deframer.frame[0..len])))
+    }
+}
+
+/// Iterator over the frames deframed from an element iterator, see [`Deframer::frames`]
+pub struct Frames<'d, I, const N: usize, T, const BUF: usize, F> {
+    deframer: &'d mut Deframer<N, T, BUF>,
+    elements: I,
+    get_frame_end: F,
+}
+
+impl<'d, I: Iterator<Item = T>, const N: usize, T: Element, const BUF: usize, F: FnMut(&mut core::slice::Iter<T>) -> Option<usize>> Iterator for Frames<'d, I, N, T, BUF, F> {
+    type Item = Result<Frame<N, T>, DeframeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for element in &mut self.elements {
+            match self.deframer.push(element, &mut self.get_frame_end) {
+                Ok(Some(frame)) => return Some(Ok(frame)),
+                Ok(None) => continue,
+                Err(error) => return Some(Err(error)),
+            }
         }
-        self.remainder[0..self.remainder_length].copy_from_slice(&data_frame[last_valid_pos..data_frame.len()]);
-       
-        // This data should be valid for the CSV parser
-        return Ok((appended, end_pos));
+        None
     }
 }
 
@@ -102,7 +1297,12 @@ impl<const N: usize> Deframer<N> {
 mod tests {
     use core::slice::Iter;
 
-    use crate::{DeframeError, Deframer};
+    use alloc::vec::Vec;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest::test_runner::TestCaseError;
+
+    use crate::{DeframeError, Deframer, Frame, Overflow};
 
     /// The frame end, which is an ASCII linebreak for these tests
     const FRAME_END: u8 = 0x0A;
@@ -112,45 +1312,60 @@ mod tests {
     #[test]
     fn finds_the_correct_frame_end() {
         let mut deframer = Deframer::<4>::new();
-        let (result, len) = deframer.deframe(&[FRAME_END, 0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        let (result, len) = deframer.deframe(&[FRAME_END, 0x01, 0x02, 0x03], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(result[0..len], [FRAME_END]);
 
         let mut deframer = Deframer::<4>::new();
-        let (result, len) = deframer.deframe(&[0x01, FRAME_END, 0x02, 0x03], GET_FRAME_END).unwrap();
+        let (result, len) = deframer.deframe(&[0x01, FRAME_END, 0x02, 0x03], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(len, 2);
         assert_eq!(result[0..len], [0x01, FRAME_END]);
 
         let mut deframer = Deframer::<4>::new();
-        let (result, len) = deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END], GET_FRAME_END).unwrap();
+        let (result, len) = deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(len, 4);
         assert_eq!(result[0..len], [0x01, 0x02, 0x03, FRAME_END]);
     }
 
+    #[test]
+    fn ends_the_frame_at_the_first_frame_end() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03], |iter: &mut Iter<u8>| iter.position(|&x| x == FRAME_END)).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x02, FRAME_END, 0x03]);
+
+        // A callback searching from the back finds the same frame end
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03], |iter: &mut Iter<u8>| iter.rposition(|&x| x == FRAME_END)).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x02, FRAME_END, 0x03]);
+    }
+
     #[test]
     fn has_the_correct_remainder() {
         let mut deframer = Deframer::<16>::new();
-        let (result, len) = deframer.deframe(&[FRAME_END, 0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        let (result, len) = deframer.deframe(&[FRAME_END, 0x01, 0x02, 0x03], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(result[0..len], [FRAME_END]);
         assert_eq!(deframer.remainder_length, 3);
 
-        let (result, len) = deframer.deframe(&[0x04, 0x05, FRAME_END, 0x06], GET_FRAME_END).unwrap();
+        let (result, len) = deframer.deframe(&[0x04, 0x05, FRAME_END, 0x06], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(deframer.remainder_length, 1);
         assert_eq!(deframer.remainder[0..deframer.remainder_length], [0x06]);
         assert_eq!(result[0..len], [0x01, 0x02, 0x03, 0x04, 0x05, FRAME_END]);
 
-        let (result, len) = deframer.deframe(&[0x07, 0x08, 0x09, 0x10, FRAME_END, 0x11, 0x22], GET_FRAME_END).unwrap();
+        let (result, len) = deframer.deframe(&[0x07, 0x08, 0x09, 0x10, FRAME_END, 0x11, 0x22], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(deframer.remainder_length, 2);
         assert_eq!(deframer.remainder[0..deframer.remainder_length], [0x11, 0x22]);
         assert_eq!(result[0..len], [0x06, 0x07, 0x08, 0x09, 0x10, FRAME_END]);
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn correctly_overflows() {
         let mut deframer = Deframer::<2>::new();
         
         // Overflows because the data_frame provider is too large for the allocated buffer
         let result = deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END], GET_FRAME_END);
-        assert_eq!(result.is_err(), true);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
 
         let mut deframer = Deframer::<2>::new();
         
@@ -164,28 +1379,1110 @@ mod tests {
         
         let result = deframer.deframe(&[0x03], GET_FRAME_END);
         assert_eq!(result.is_err(), true);
-        assert_eq!(result.err().unwrap(), DeframeError::Overflow);
+        assert_eq!(result.err().unwrap(), DeframeError::Overflow(Overflow::RemainderExhausted));
     }
 
     #[test]
-    fn remainder_increases() {
-        let mut deframer = Deframer::<4>::new();
-      
-        let (_data, len) = deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
-        assert_eq!(deframer.remainder_length, 1);
-        assert_eq!(len, 0);
-      
-        let (_data, len) = deframer.deframe(&[0x02], GET_FRAME_END).unwrap();
-        assert_eq!(deframer.remainder_length, 2);
-        assert_eq!(len, 0);
-      
-        let (_data, len) = deframer.deframe(&[0x03], GET_FRAME_END).unwrap();
+    fn keeps_the_frames_after_an_oversized_frame() {
+        let mut deframer = Deframer::<4, u8, 8>::new();
+        let result = deframer.deframe(&[0x01, 0x02, 0x03, 0x04, 0x05, FRAME_END, 0x06, FRAME_END], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert_eq!(deframer.dropped_bytes(), 6);
+        assert_eq!(deframer.consumed(), 6);
+        assert_eq!(*deframer.deframe(&[], GET_FRAME_END).unwrap(), [0x06, FRAME_END]);
+
+        // The oversized frame started in the remainder
+        deframer.deframe(&[0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        let result = deframer.deframe(&[0x04, 0x05, FRAME_END, 0x07, FRAME_END, 0x08], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert_eq!(deframer.remainder(), [0x07, FRAME_END, 0x08]);
+        assert_eq!(*deframer.deframe(&[], GET_FRAME_END).unwrap(), [0x07, FRAME_END]);
+    }
+
+    #[test]
+    fn deframes_chained_parts() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_chained(&[&[0x01, 0x02], &[0x03, FRAME_END, 0x04]], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x04]);
+
+        // The frame end is at the very end of the first part
+        let (data, len) = deframer.deframe_chained(&[&[0x05, FRAME_END], &[0x06, 0x07]], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, 0x05, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x06, 0x07]);
+
+        let (_data, len) = deframer.deframe_chained(&[&[0x08], &[], &[0x09]], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert_eq!(deframer.remainder(), [0x06, 0x07, 0x08, 0x09]);
+
+        let result = deframer.deframe_chained(&[&[0x0B; 3], &[0x0C, 0x0D]], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert_eq!(deframer.dropped_bytes(), 9);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn deframes_frames_ending_exactly_at_capacity() {
+        let mut deframer = Deframer::<4>::new();
+        let (data, len) = deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END, 0x04, 0x05, 0x06, 0x07], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data, [0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(len, 4);
+        assert_eq!(deframer.remainder(), [0x04, 0x05, 0x06, 0x07]);
+
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        let (data, len) = deframer.deframe(&[0x03, FRAME_END, 0x04, 0x05, 0x06, 0x07], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data, [0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(len, 4);
+        assert_eq!(deframer.remainder(), [0x04, 0x05, 0x06, 0x07]);
+    }
+
+    #[test]
+    fn overflows_when_the_rest_exceeds_capacity() {
+        let mut deframer = Deframer::<4>::new();
+        let result = deframer.deframe(&[0x01, FRAME_END, 0x02, 0x03, 0x04, 0x05, 0x06], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn overflows_rather_than_panicking_on_oversized_input() {
+        let mut deframer = Deframer::<4>::new();
+        let result = deframer.deframe(&[0x01, 0x02, 0x03, 0x04, 0x05], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert_eq!(deframer.dropped_bytes(), 5);
+
+        let result = deframer.deframe_slice(&[0x01; 16], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn keeps_the_remainder_length_consistent_over_repeated_searches() {
+        // Searching from the back, and from part way into the iterator, both take several calls
+        // to find the earliest frame end
+        let mut deframer = Deframer::<4, u8, 8>::new();
+        let frame = deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03, FRAME_END, 0x04], |iter| iter.rposition(|&x| x == FRAME_END)).unwrap();
+        assert_eq!(*frame, [0x01, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x02, FRAME_END, 0x03, FRAME_END, 0x04]);
+
+        let skip_first = |iter: &mut Iter<u8>| {
+            iter.next();
+            iter.position(|&x| x == FRAME_END).map(|pos| pos + 1)
+        };
+        let frame = deframer.deframe(&[FRAME_END], skip_first).unwrap();
+        assert_eq!(*frame, [0x02, FRAME_END]);
+        let frame = deframer.deframe(&[], skip_first).unwrap();
+        assert_eq!(*frame, [0x03, FRAME_END]);
+        let frame = deframer.deframe(&[], skip_first).unwrap();
+        assert_eq!(*frame, [0x04, FRAME_END]);
+        assert_eq!(deframer.remainder_len(), 0);
+        assert!(deframer.deframe(&[], skip_first).unwrap().is_empty());
+    }
+
+    #[test]
+    fn finds_frame_ends_at_absolute_positions_after_a_remainder() {
+        // Each search only covers the data before the last frame end found, so the positions are
+        // always relative to the start of the data rather than to where a search left off
+        let mut searches = 0;
+        let mut deframer = Deframer::<8>::new();
+        deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
+        let frame = deframer.deframe(&[0x02, FRAME_END, 0x03, 0x04, FRAME_END, 0x05, FRAME_END, 0x06], |iter: &mut Iter<u8>| {
+            searches += 1;
+            iter.rposition(|&x| x == FRAME_END)
+        }).unwrap();
+        assert_eq!(*frame, [0x01, 0x02, FRAME_END]);
+        assert!(searches > 2);
+        assert_eq!(deframer.remainder(), [0x03, 0x04, FRAME_END, 0x05, FRAME_END, 0x06]);
+
+        let frame = deframer.deframe_chained(&[&[0x07, FRAME_END]], |iter| iter.rposition(|&x| x == FRAME_END)).unwrap();
+        assert_eq!(*frame, [0x03, 0x04, FRAME_END]);
+        let frame = deframer.deframe(&[], |iter| iter.rposition(|&x| x == FRAME_END)).unwrap();
+        assert_eq!(*frame, [0x05, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x06, 0x07, FRAME_END]);
+    }
+
+    #[test]
+    fn deframes_with_a_capturing_closure() {
+        let delimiter = 0x7E;
+        let mut searches = 0;
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe(&[0x01, 0x7E, 0x02], |iter: &mut Iter<u8>| {
+            searches += 1;
+            iter.rposition(|&x| x == delimiter)
+        }).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x7E]);
+        // Once to find the frame end, and once more for any earlier one before it
+        assert_eq!(searches, 2);
+    }
+
+    #[test]
+    fn counts_dropped_bytes() {
+        let mut deframer = Deframer::<2>::new();
+        deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.dropped_bytes(), 0);
+
+        // The remainder is flushed along with the data frame
+        let result = deframer.deframe(&[0x02, 0x03], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert_eq!(deframer.dropped_bytes(), 3);
+        assert!(deframer.is_empty());
+
+        let (data, len) = deframer.deframe(&[0x04, FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, FRAME_END]);
+
+        let mut frames = deframer.deframe_all(&[0x01, 0x02, FRAME_END, 0x03, FRAME_END], GET_FRAME_END);
+        assert_eq!(frames.next().unwrap(), Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert!(frames.next().unwrap().is_ok());
+        assert!(frames.next().is_none());
+        assert_eq!(deframer.dropped_bytes(), 6);
+    }
+
+    #[test]
+    fn remainder_increases() {
+        let mut deframer = Deframer::<4>::new();
+      
+        let (_data, len) = deframer.deframe(&[0x01], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(deframer.remainder_length, 1);
+        assert_eq!(len, 0);
+      
+        let (_data, len) = deframer.deframe(&[0x02], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(deframer.remainder_length, 2);
+        assert_eq!(len, 0);
+      
+        let (_data, len) = deframer.deframe(&[0x03], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(deframer.remainder_length, 3);
         assert_eq!(len, 0);
       
-        let (data, len) = deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap();
+        let (data, len) = deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap().into_parts();
         assert_eq!(deframer.remainder_length, 0);
         assert_eq!(len, 4);
         assert_eq!(data, [0x01, 0x02, 0x03, FRAME_END]);
     }
-}   
\ No newline at end of file
+
+    #[test]
+    fn rejects_frames_longer_than_the_maximum() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_max_frame_len(Some(2));
+
+        let (data, len) = deframer.deframe(&[0x01, 0x02, FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02, FRAME_END]);
+
+        assert_eq!(deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END], GET_FRAME_END), Err(DeframeError::FrameTooLong));
+        assert_eq!(deframer.dropped_bytes(), 4);
+
+        // A runaway frame is cut off well before the buffer overflows
+        assert!(deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap().is_empty());
+        assert_eq!(deframer.deframe(&[0x03], GET_FRAME_END), Err(DeframeError::FrameTooLong));
+        assert_eq!(deframer.dropped_bytes(), 7);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn reports_the_consumed_elements() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.deframe(&[0x01, FRAME_END, 0x02, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.consumed(), 2);
+        assert_eq!(deframer.remainder(), [0x02, 0x03]);
+
+        deframer.deframe(&[0x04], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.consumed(), 0);
+
+        deframer.deframe(&[FRAME_END, 0x05], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.consumed(), 1);
+
+        let mut deframer = Deframer::<8>::with_delimiter(FRAME_END);
+        deframer.deframe_delimited(&[0x01, FRAME_END, FRAME_END, 0x02]).unwrap();
+        assert_eq!(deframer.consumed(), 3);
+    }
+
+    #[test]
+    fn resyncs_on_the_next_frame_end() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        deframer.resync();
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 2);
+
+        // Everything up to and including the next frame end is skipped
+        let (_data, len) = deframer.deframe(&[0x03], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert!(deframer.is_empty());
+        let (_data, len) = deframer.deframe(&[0x04, FRAME_END, 0x05], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert_eq!(deframer.remainder(), [0x05]);
+        assert_eq!(deframer.dropped_bytes(), 5);
+
+        let (data, len) = deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x05, FRAME_END]);
+    }
+
+    #[test]
+    fn can_be_constructed_at_compile_time() {
+        static DEFRAMER: Deframer<4> = Deframer::new();
+        assert!(DEFRAMER.is_empty());
+    }
+
+    #[test]
+    fn configures_a_deframer_at_compile_time() {
+        const CONFIGURED: Deframer<8> = Deframer::with_config(FRAME_END, true, 2);
+        let mut deframer = CONFIGURED;
+
+        let (data, len) = deframer.deframe_delimited(&[0x01, 0x02, FRAME_END]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02]);
+        let result = deframer.deframe_delimited(&[0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(result, Err(DeframeError::FrameTooLong));
+    }
+
+    #[test]
+    fn resumes_from_a_saved_remainder() {
+        let mut deframer = Deframer::<4>::from_parts(&[0x01, FRAME_END, 0x02]).unwrap();
+        let (data, len) = deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        let (data, len) = deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, FRAME_END]);
+
+        let result = Deframer::<4>::from_parts(&[0x01; 5]);
+        assert_eq!(result.err(), Some(DeframeError::Overflow(Overflow::RemainderExhausted)));
+    }
+
+    #[test]
+    fn zeroes_elements_leaving_the_remainder() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        deframer.deframe(&[FRAME_END, 0x04], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder, [0x04, 0, 0, 0]);
+
+        deframer.remainder[3] = 0xFF;
+        deframer.compact();
+        assert_eq!(deframer.remainder, [0x04, 0, 0, 0]);
+    }
+
+    #[test]
+    fn restores_a_saved_copy() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
+        let saved = deframer;
+        assert_eq!(*deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap(), [0x01, FRAME_END]);
+
+        deframer = saved;
+        assert_eq!(deframer.remainder(), [0x01]);
+        assert_eq!(*deframer.deframe(&[0x02, FRAME_END], GET_FRAME_END).unwrap(), [0x01, 0x02, FRAME_END]);
+    }
+
+    #[test]
+    fn compares_only_the_valid_remainder() {
+        let mut a = Deframer::<4>::new();
+        let mut b = Deframer::<4>::new();
+        assert_eq!(a, b);
+
+        a.deframe(&[0x01, 0x02, FRAME_END, 0x03], GET_FRAME_END).unwrap();
+        b.deframe(&[0x03], GET_FRAME_END).unwrap();
+        assert_eq!(a, b);
+
+        b.deframe(&[0x04], GET_FRAME_END).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn debug_shows_the_remainder() {
+        use core::fmt::Write;
+
+        struct Buffer {
+            data: [u8; 64],
+            len: usize,
+        }
+
+        impl Write for Buffer {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                let end = self.len + s.len();
+                self.data.get_mut(self.len..end).ok_or(core::fmt::Error)?.copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+
+        let mut deframer = Deframer::<16>::new();
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        let mut buffer = Buffer { data: [0; 64], len: 0 };
+        write!(buffer, "{:?}", deframer).unwrap();
+        assert_eq!(&buffer.data[0..buffer.len], b"Deframer { remainder_length: 2, remainder: [1, 2], .. }");
+    }
+
+    #[test]
+    fn reports_its_capacity() {
+        assert_eq!(Deframer::<32>::new().capacity(), 32);
+        assert_eq!(Deframer::<4, u16>::new().capacity(), 4);
+    }
+
+    #[test]
+    fn buffers_more_than_it_returns() {
+        let mut deframer = Deframer::<2, u8, 6>::new();
+        assert_eq!(deframer.remainder_capacity(), 6);
+
+        // A burst of frames is buffered even though each frame is short
+        let (data, len) = deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03, FRAME_END, 0x04, 0x05], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        assert_eq!(deframer.remainder().len(), 6);
+
+        // Only a frame that can't be returned overflows
+        let mut deframer = Deframer::<2, u8, 6>::new();
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        let result = deframer.deframe(&[FRAME_END], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+    }
+
+    #[test]
+    fn peeks_for_a_buffered_frame() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        assert!(!deframer.has_frame(GET_FRAME_END));
+
+        // Only the first frame is returned, so the second stays buffered
+        deframer.deframe(&[0x03, FRAME_END, 0x04, FRAME_END], GET_FRAME_END).unwrap();
+        assert!(deframer.has_frame(GET_FRAME_END));
+        assert_eq!(deframer.remainder(), [0x04, FRAME_END]);
+
+        // A position past the end of the remainder isn't a frame end
+        assert!(!deframer.has_frame(|iter| Some(iter.len())));
+    }
+
+    #[test]
+    fn counts_the_buffered_frames() {
+        let mut deframer = Deframer::<4, u8, 8>::new();
+        assert_eq!(deframer.count_frames(GET_FRAME_END), 0);
+        deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, FRAME_END, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.count_frames(GET_FRAME_END), 2);
+        deframer.set_skip_empty_frames(true);
+        assert_eq!(deframer.count_frames(GET_FRAME_END), 1);
+        assert_eq!(deframer.remainder(), [0x02, FRAME_END, FRAME_END, 0x03]);
+    }
+
+    #[test]
+    fn peeks_at_the_next_frame_without_taking_it() {
+        let mut deframer = Deframer::<4, u8, 8>::new();
+        assert_eq!(deframer.peek_frame(GET_FRAME_END), None);
+        deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03], GET_FRAME_END).unwrap();
+
+        let frame = deframer.peek_frame(GET_FRAME_END).unwrap();
+        assert_eq!(*frame, [0x02, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x02, FRAME_END, 0x03]);
+        assert_eq!(deframer.deframe(&[], GET_FRAME_END).unwrap(), frame);
+        assert_eq!(deframer.peek_frame(GET_FRAME_END), None);
+    }
+
+    #[test]
+    fn defaults_to_an_empty_deframer() {
+        let deframer = Deframer::<4>::default();
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.remainder, [0; 4]);
+    }
+
+    #[test]
+    fn reset_discards_the_remainder() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder_length, 2);
+
+        deframer.reset();
+        assert_eq!(deframer.remainder_length, 0);
+        assert_eq!(deframer.remainder, [0; 4]);
+
+        let (data, len) = deframer.deframe(&[0x03, FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x03, FRAME_END]);
+    }
+
+    #[test]
+    fn exposes_the_remainder() {
+        let mut deframer = Deframer::<4>::new();
+        assert_eq!(deframer.remainder(), []);
+        assert_eq!(deframer.remainder_len(), 0);
+        assert!(deframer.is_empty());
+
+        deframer.deframe(&[0x01, FRAME_END, 0x02, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder(), [0x02, 0x03]);
+        assert_eq!(deframer.remainder_len(), 2);
+        assert!(!deframer.is_empty());
+    }
+
+    #[test]
+    fn deframes_on_the_configured_delimiter() {
+        let mut deframer = Deframer::<8>::with_delimiter(0x00);
+        let (data, len) = deframer.deframe_delimited(&[0x01, FRAME_END, 0x00, 0x02]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END, 0x00]);
+        assert_eq!(deframer.remainder(), [0x02]);
+
+        let (data, len) = deframer.deframe_delimited(&[0x03, 0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, 0x03, 0x00]);
+        assert!(deframer.is_empty());
+
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_delimited(&[0x01, 0x00, 0x02]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x00]);
+    }
+
+    #[test]
+    fn deframes_on_any_of_several_delimiters() {
+        const CR: u8 = 0x0D;
+        let mut deframer = Deframer::<8>::with_delimiters(&[CR, FRAME_END, 0x00]);
+
+        let (data, len) = deframer.deframe_delimited(&[0x01, 0x00, 0x02]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x00]);
+
+        // CR LF is a single boundary
+        let (data, len) = deframer.deframe_delimited(&[0x03, CR, FRAME_END, 0x04]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, 0x03, CR]);
+        assert_eq!(deframer.remainder(), [0x04]);
+
+        // Even when split across data frames
+        let (data, len) = deframer.deframe_delimited(&[CR]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, CR]);
+        let (_data, len) = deframer.deframe_delimited(&[FRAME_END]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (_data, len) = deframer.deframe_delimited(&[FRAME_END, 0x05]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert_eq!(deframer.remainder(), [0x05]);
+
+        deframer.set_strip_delimiter(true);
+        let (data, len) = deframer.deframe_delimited(&[CR, FRAME_END, CR, FRAME_END]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x05]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn deframes_non_byte_elements() {
+        const SENTINEL: u16 = 0xFFFF;
+        let mut deframer = Deframer::<8, u16>::with_delimiter(SENTINEL);
+
+        let (data, len) = deframer.deframe_delimited(&[0x0100, 0x0200, SENTINEL, 0x0300]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x0100, 0x0200, SENTINEL]);
+        assert_eq!(deframer.remainder(), [0x0300]);
+
+        let (data, len) = deframer.deframe(&[0x0400, SENTINEL], |iter| iter.rposition(|&x| x == SENTINEL)).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x0300, 0x0400, SENTINEL]);
+    }
+
+    #[test]
+    fn deframes_into_the_internal_buffer() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.deframe_slice(&[0x01, FRAME_END, 0x02], GET_FRAME_END).unwrap(), [0x01, FRAME_END]);
+        assert_eq!(deframer.deframe_slice(&[0x03], GET_FRAME_END).unwrap(), []);
+        assert_eq!(deframer.deframe_slice(&[FRAME_END], GET_FRAME_END).unwrap(), [0x02, 0x03, FRAME_END]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn drains_several_frames_from_one_chunk_in_order() {
+        let mut deframer = Deframer::<4, u8, 16>::new();
+        let (data, len) = deframer.deframe(b"a\nb\n", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"a\n");
+        let (data, len) = deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"b\n");
+        assert!(deframer.is_empty());
+
+        let (data, len) = deframer.deframe(b"a\nb\nc\n", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"a\n");
+        let (data, len) = deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"b\n");
+        let (data, len) = deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"c\n");
+        assert_eq!(deframer.next_frame(&[], GET_FRAME_END).unwrap(), None);
+
+        // New data is queued behind the frames that are already buffered
+        deframer.set_strip_delimiter(true);
+        let (data, len) = deframer.deframe(b"a\nb\nc\nd\n", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"a");
+        let (data, len) = deframer.deframe(b"e", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"b");
+        let (data, len) = deframer.deframe(b"\n", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"c");
+        let (data, len) = deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"d");
+        let (data, len) = deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"e");
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn drains_buffered_frames_in_every_mode() {
+        let mut deframer = Deframer::<4, u8, 16>::with_delimiter(FRAME_END);
+        let (data, len) = deframer.deframe_delimited(b"a\n\nb\nc").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"a\n");
+        let (data, len) = deframer.deframe_delimited(b"\n").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"b\n");
+        let (data, len) = deframer.deframe_chained(&[b"", b"d"], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"c\n");
+        assert_eq!(deframer.remainder(), b"d");
+
+        deframer.deframe(b"\ne\nf", GET_FRAME_END).unwrap();
+        let mut frames = deframer.deframe_all(b"\n", GET_FRAME_END);
+        assert_eq!(frames.next().unwrap().unwrap().into_parts(), (*b"e\n\0\0", 2));
+        assert_eq!(frames.next().unwrap().unwrap().into_parts(), (*b"f\n\0\0", 2));
+        assert_eq!(frames.next(), None);
+    }
+
+    #[test]
+    fn finds_a_frame_end_already_in_the_remainder() {
+        const OTHER_END: u8 = 0x0B;
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, OTHER_END, 0x02], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder(), [0x01, OTHER_END, 0x02]);
+
+        // The frame end is entirely within the remainder once the frame end changes
+        let get_other_end = |iter: &mut Iter<u8>| iter.position(|&x| x == OTHER_END);
+        let (data, len) = deframer.deframe(&[0x03], get_other_end).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, OTHER_END]);
+        assert_eq!(deframer.remainder(), [0x02, 0x03]);
+    }
+
+    #[test]
+    fn switches_mode_without_losing_the_remainder() {
+        // A line based handshake, followed by COBS frames in the same data frame
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_crlf(b"OK\r\n\x03\x11\x22").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"OK");
+        deframer.switch_mode();
+        let (data, len) = deframer.deframe_cobs(&[0x00]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x11, 0x22]);
+
+        // A delimiter ending the last frame of the old framing doesn't skip the new data
+        let mut deframer = Deframer::<8>::with_delimiter(FRAME_END);
+        deframer.deframe_delimited(&[0x01, FRAME_END]).unwrap();
+        deframer.switch_mode();
+        assert_eq!(deframer.next_frame_delimited(&[FRAME_END]).unwrap().map(Frame::into_parts), Some(([FRAME_END, 0, 0, 0, 0, 0, 0, 0], 1)));
+
+        let mut deframer = Deframer::<8>::new();
+        deframer.deframe_crlf(b"OK\r").unwrap();
+        deframer.switch_mode();
+        assert_eq!(deframer.next_frame_crlf(b"\n").unwrap().map(Frame::into_parts), Some(([0; 8], 0)));
+    }
+
+    #[test]
+    fn flushes_the_remainder_as_a_final_frame() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, FRAME_END, 0x02, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.flush().into_parts(), ([0x02, 0x03, 0, 0], 2));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.flush().into_parts(), ([0; 4], 0));
+
+        // A remainder larger than the frame buffer is flushed over several calls
+        let mut deframer = Deframer::<2, u8, 4>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.flush().into_parts(), ([0x01, 0x02], 2));
+        assert_eq!(deframer.flush().into_parts(), ([0x03, 0], 1));
+    }
+
+    #[test]
+    fn clears_and_returns_the_remainder() {
+        let mut deframer = Deframer::<2, u8, 4>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.clear_and_return().into_parts(), ([0x01, 0x02], 2));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 1);
+    }
+
+    #[test]
+    fn returns_the_unused_tail() {
+        let mut deframer = Deframer::<4>::new();
+        let (frame, tail) = deframer.deframe_with_tail(&[0x01, FRAME_END, 0x02, 0x03], GET_FRAME_END).unwrap();
+        assert_eq!(*frame, [0x01, FRAME_END]);
+        assert_eq!(tail, [0x02, 0x03]);
+
+        let (frame, tail) = deframer.deframe_with_tail(&[FRAME_END], GET_FRAME_END).unwrap();
+        assert_eq!(frame.len(), 3);
+        assert_eq!(tail, []);
+    }
+
+    #[test]
+    fn deframes_into_a_caller_provided_buffer() {
+        let mut deframer = Deframer::<8>::new();
+        let mut out = [0xFF; 4];
+        assert_eq!(deframer.deframe_into(&[0x01, 0x02], &mut out, GET_FRAME_END), Ok(0));
+        assert_eq!(deframer.deframe_into(&[FRAME_END, 0x03], &mut out, GET_FRAME_END), Ok(3));
+        assert_eq!(out, [0x01, 0x02, FRAME_END, 0xFF]);
+
+        // A shorter frame leaves the end of the longer one in place
+        assert_eq!(deframer.deframe_into(&[0x07, FRAME_END], &mut out, GET_FRAME_END), Ok(3));
+        assert_eq!(out, [0x03, 0x07, FRAME_END, 0xFF]);
+        assert_eq!(deframer.deframe_into(&[FRAME_END], &mut out, GET_FRAME_END), Ok(1));
+        assert_eq!(out, [FRAME_END, 0x07, FRAME_END, 0xFF]);
+
+        // The frame fits the deframer but not the buffer
+        let result = deframer.deframe_into(&[0x04, 0x05, 0x06, 0x08, FRAME_END], &mut out, GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn distinguishes_no_frame_from_a_frame() {
+        let mut deframer = Deframer::<4>::new();
+        assert_eq!(deframer.next_frame(&[0x01], GET_FRAME_END).unwrap(), None);
+        assert_eq!(deframer.next_frame(&[], GET_FRAME_END).unwrap(), None);
+
+        let (data, len) = deframer.next_frame(&[FRAME_END], GET_FRAME_END).unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        assert_eq!(deframer.next_frame(&[], GET_FRAME_END).unwrap(), None);
+    }
+
+    #[test]
+    fn distinguishes_no_frame_from_an_empty_delimited_frame() {
+        let mut deframer = Deframer::<4>::with_delimiter(FRAME_END);
+        deframer.set_strip_delimiter(true);
+
+        assert_eq!(deframer.next_frame_delimited(&[FRAME_END]).unwrap().map(Frame::into_parts), Some(([0; 4], 0)));
+        assert_eq!(deframer.next_frame_delimited(&[0x01]).unwrap(), None);
+        assert_eq!(deframer.next_frame_delimited(&[FRAME_END]).unwrap().map(Frame::into_parts), Some(([0x01, 0, 0, 0], 1)));
+
+        // The rest of a run of delimiters is skipped rather than producing empty frames
+        assert_eq!(deframer.next_frame_delimited(&[FRAME_END]).unwrap(), None);
+    }
+
+    #[test]
+    fn skips_empty_frames() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.set_strip_delimiter(true);
+        deframer.set_skip_empty_frames(true);
+
+        // Including a bare frame end at the very start of a data frame
+        let (data, len) = deframer.deframe(&[FRAME_END, 0x01, FRAME_END, FRAME_END, 0x02], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01]);
+        assert_eq!(deframer.next_frame(&[FRAME_END], GET_FRAME_END).unwrap().map(Frame::into_parts), Some(([0x02, 0, 0, 0], 1)));
+        assert_eq!(deframer.next_frame(&[FRAME_END, FRAME_END], GET_FRAME_END).unwrap(), None);
+        assert!(deframer.is_empty());
+
+        let mut frames = deframer.deframe_all(&[0x03, FRAME_END, FRAME_END, 0x04, FRAME_END], GET_FRAME_END);
+        assert_eq!(frames.next().unwrap().unwrap().into_parts(), ([0x03, 0, 0, 0], 1));
+        assert_eq!(frames.next().unwrap().unwrap().into_parts(), ([0x04, 0, 0, 0], 1));
+        assert_eq!(frames.next(), None);
+    }
+
+    #[test]
+    fn skips_frames_shorter_than_the_minimum() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_min_frame_len(Some(3));
+        let frame = deframer.deframe(&[0x01, FRAME_END, FRAME_END, 0x02, 0x03, 0x04, FRAME_END], GET_FRAME_END).unwrap();
+        assert_eq!(*frame, [0x02, 0x03, 0x04, FRAME_END]);
+
+        // A short frame split across data frames, and one already buffered
+        deframer.deframe(&[0x05, 0x06], GET_FRAME_END).unwrap();
+        let frame = deframer.deframe(&[FRAME_END, 0x07, FRAME_END, 0x08, 0x09, 0x0B], GET_FRAME_END).unwrap();
+        assert!(frame.is_empty());
+        assert_eq!(deframer.remainder(), [0x08, 0x09, 0x0B]);
+        let frame = deframer.deframe(&[FRAME_END, 0x0C, FRAME_END], GET_FRAME_END).unwrap();
+        assert_eq!(*frame, [0x08, 0x09, 0x0B, FRAME_END]);
+        assert_eq!(deframer.count_frames(GET_FRAME_END), 0);
+        assert!(deframer.deframe(&[], GET_FRAME_END).unwrap().is_empty());
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 0);
+    }
+
+    #[test]
+    fn rejects_frame_ends_outside_of_the_data() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
+        let result = deframer.deframe(&[0x02, 0x03], |iter| Some(iter.len()));
+        assert_eq!(result, Err(DeframeError::InvalidFrameEnd));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 3);
+
+        let result = deframer.deframe_chained(&[&[0x01], &[0x02]], |iter| iter.position(|&x| x == 0x02).map(|_| usize::MAX));
+        assert_eq!(result, Err(DeframeError::InvalidFrameEnd));
+
+        let mut frames = deframer.deframe_all(&[0x01, FRAME_END, 0x02], |iter| iter.position(|&x| x == FRAME_END).map(|pos| pos + 2));
+        assert_eq!(frames.next(), Some(Err(DeframeError::InvalidFrameEnd)));
+        assert_eq!(frames.next(), None);
+        assert!(deframer.is_empty());
+
+        // The deframer carries on as normal with a valid `get_frame_end`
+        let (data, len) = deframer.deframe(&[0x04, FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, FRAME_END]);
+    }
+
+    #[test]
+    fn strips_the_delimiter() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.set_strip_delimiter(true);
+
+        let (data, len) = deframer.deframe(&[0x01, FRAME_END, 0x02], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data, [0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(len, 1);
+
+        let (_data, len) = deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(len, 1);
+        assert_eq!(deframer.next_frame(&[FRAME_END], GET_FRAME_END).unwrap().map(Frame::into_parts), Some(([0; 4], 0)));
+
+        let mut frames = deframer.deframe_all(&[0x03, FRAME_END, FRAME_END], GET_FRAME_END);
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x03]);
+        let (_data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(len, 0);
+        assert!(frames.next().is_none());
+    }
+
+    #[test]
+    fn pushes_single_bytes() {
+        let mut deframer = Deframer::<4>::new();
+        assert_eq!(deframer.push(0x01, GET_FRAME_END), Ok(None));
+        assert_eq!(deframer.push(0x02, GET_FRAME_END), Ok(None));
+        assert_eq!(deframer.push(FRAME_END, GET_FRAME_END).unwrap().map(Frame::into_parts), Some(([0x01, 0x02, FRAME_END, 0x00], 3)));
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn deframes_frames_from_an_iterator() {
+        let mut deframer = Deframer::<4>::new();
+        let bytes = [0x01, FRAME_END, 0x02, 0x03, FRAME_END, 0x04];
+        let mut frames = deframer.frames(bytes.iter().copied(), GET_FRAME_END);
+
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, 0x03, FRAME_END]);
+        assert!(frames.next().is_none());
+        assert_eq!(deframer.remainder(), [0x04]);
+
+        let mut frames = deframer.frames([0x05, 0x06, 0x07, 0x08].iter().copied(), GET_FRAME_END);
+        assert_eq!(frames.next(), Some(Err(DeframeError::Overflow(Overflow::RemainderExhausted))));
+    }
+
+    #[test]
+    fn deframes_all_frames() {
+        let mut deframer = Deframer::<8>::new();
+        let mut frames = deframer.deframe_all(&[0x01, FRAME_END, 0x02, 0x03, FRAME_END, FRAME_END, 0x04], GET_FRAME_END);
+
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, FRAME_END]);
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02, 0x03, FRAME_END]);
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [FRAME_END]);
+        assert!(frames.next().is_none());
+        assert_eq!(deframer.remainder_length, 1);
+
+        let mut frames = deframer.deframe_all(&[0x05, FRAME_END], GET_FRAME_END);
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, 0x05, FRAME_END]);
+        assert!(frames.next().is_none());
+        assert_eq!(deframer.remainder_length, 0);
+    }
+
+    #[test]
+    fn calls_back_with_each_frame() {
+        let mut deframer = Deframer::<4>::new();
+        let mut frames = 0;
+        let result = deframer.deframe_each(&[0x01, FRAME_END, 0x02, 0x03, 0x04, 0x05, FRAME_END, FRAME_END, 0x06], GET_FRAME_END, |frame| {
+            match frames {
+                0 => assert_eq!(frame, [0x01, FRAME_END]),
+                1 => assert_eq!(frame, [FRAME_END]),
+                _ => unreachable!(),
+            }
+            frames += 1;
+        });
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert_eq!(frames, 2);
+        assert_eq!(deframer.remainder(), [0x06]);
+    }
+
+    #[test]
+    fn deframe_all_overflows_per_frame() {
+        let mut deframer = Deframer::<2>::new();
+        let mut frames = deframer.deframe_all(&[0x01, 0x02, FRAME_END, 0x03, FRAME_END], GET_FRAME_END);
+
+        assert_eq!(frames.next().unwrap(), Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        let (data, len) = frames.next().unwrap().unwrap().into_parts();
+        assert_eq!(data[0..len], [0x03, FRAME_END]);
+        assert!(frames.next().is_none());
+    }
+
+    /// Generates a stream of elements with a frame end at least every `N` elements, so that no
+    /// frame overflows
+    fn stream<const N: usize>() -> impl Strategy<Value = Vec<u8>> {
+        vec(prop_oneof![1 => Just(FRAME_END), 3 => 1u8..4], 0..128).prop_map(|mut stream| {
+            let mut since_end = 0;
+            for x in stream.iter_mut() {
+                if since_end == N - 1 {
+                    *x = FRAME_END;
+                }
+                since_end = if *x == FRAME_END { 0 } else { since_end + 1 };
+            }
+            stream
+        })
+    }
+
+    /// Generates a stream of CR LF terminated lines, whose payloads hold lone CRs and LFs, followed
+    /// by an unterminated one
+    fn line_stream() -> impl Strategy<Value = Vec<u8>> {
+        let payload = || vec(prop_oneof![Just(0x01), Just(0x02), Just(b'\r'), Just(FRAME_END)], 0..5);
+        (vec(payload(), 0..24), payload()).prop_map(|(lines, tail)| {
+            let mut stream = Vec::new();
+            for (i, payload) in lines.iter().chain(core::iter::once(&tail)).enumerate() {
+                for &x in payload {
+                    // A CR followed by an LF would end the line early
+                    stream.push(if x == FRAME_END && stream.last() == Some(&b'\r') { 0x01 } else { x });
+                }
+                if i < lines.len() {
+                    stream.extend_from_slice(b"\r\n");
+                }
+            }
+            stream
+        })
+    }
+
+    /// Generates the lengths of the chunks a stream is fed in, which are used in turn and start
+    /// over once they run out. Some may be empty, but not all of them.
+    fn chunk_sizes() -> impl Strategy<Value = Vec<usize>> {
+        vec(0usize..9, 1..16).prop_filter("chunk sizes that are all zero", |sizes| sizes.iter().any(|&size| size > 0))
+    }
+
+    /// Feeds `stream` to `deframe_chunk` in chunks of `chunk_sizes`, checking that the frames it
+    /// emits add up to the first `last_end` elements of the stream, and that the rest is left
+    /// buffered
+    fn check_chunk_splits(stream: &[u8], chunk_sizes: &[usize], last_end: usize, mut deframer: Deframer<8, u8, 16>, mut deframe_chunk: impl FnMut(&mut Deframer<8, u8, 16>, &[u8], &mut dyn FnMut(&[u8]))) -> Result<(), TestCaseError> {
+        let mut out = Vec::new();
+        let mut sizes = chunk_sizes.iter().cycle();
+        let mut rest = stream;
+        while !rest.is_empty() {
+            let (chunk, next) = rest.split_at((*sizes.next().unwrap()).min(rest.len()));
+            deframe_chunk(&mut deframer, chunk, &mut |frame| out.extend_from_slice(frame));
+            rest = next;
+        }
+
+        prop_assert_eq!(&out[..], &stream[0..last_end]);
+        prop_assert_eq!(deframer.remainder(), &stream[last_end..stream.len()]);
+        prop_assert_eq!(deframer.dropped_bytes(), 0);
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn fails_safe_on_any_restored_remainder(saved in vec(prop_oneof![1 => Just(FRAME_END), 3 => 0x80u8..], 17), len in 0usize..17) {
+            let result = Deframer::<4, u8, 16>::from_parts(&saved);
+            prop_assert_eq!(result.err(), Some(DeframeError::Overflow(Overflow::RemainderExhausted)));
+
+            // Whatever was restored is deframed as data, with frames too long for N as errors
+            let mut deframer = Deframer::<4, u8, 16>::from_parts(&saved[0..len]).unwrap();
+            for _ in 0..16 {
+                if let Ok(None) = deframer.next_frame(&[], GET_FRAME_END) {
+                    break;
+                }
+            }
+            let _ = deframer.deframe(&[FRAME_END], GET_FRAME_END);
+            prop_assert!(deframer.is_empty());
+            prop_assert_eq!(&*deframer.deframe(&[0x01, FRAME_END], GET_FRAME_END).unwrap(), &[0x01, FRAME_END]);
+        }
+
+        #[test]
+        fn never_loses_or_duplicates_elements_across_chunk_splits(stream in stream::<8>(), chunk_sizes in chunk_sizes()) {
+            let last_end = stream.iter().rposition(|&x| x == FRAME_END).map_or(0, |pos| pos + 1);
+            let deframer = Deframer::<8, u8, 16>::new;
+
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, mut chunk, emit| {
+                while let Some((data, len)) = deframer.next_frame(core::mem::take(&mut chunk), GET_FRAME_END).unwrap().map(Frame::into_parts) {
+                    emit(&data[0..len]);
+                }
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, chunk, emit| {
+                deframer.deframe_each(chunk, GET_FRAME_END, |frame| emit(frame)).unwrap();
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, chunk, emit| {
+                for frame in deframer.deframe_all(chunk, GET_FRAME_END) {
+                    let (data, len) = frame.unwrap().into_parts();
+                    emit(&data[0..len]);
+                }
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, chunk, emit| {
+                let mut parts = [&chunk[0..chunk.len() / 2], &chunk[chunk.len() / 2..chunk.len()]];
+                while deframer.has_frame(GET_FRAME_END) || !parts[0].is_empty() || !parts[1].is_empty() {
+                    let (data, len) = deframer.deframe_chained(&parts, GET_FRAME_END).unwrap().into_parts();
+                    emit(&data[0..len]);
+                    parts = [&[], &[]];
+                }
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, chunk, emit| {
+                let (frame, tail) = deframer.deframe_with_tail(chunk, GET_FRAME_END).unwrap();
+                emit(&frame);
+                assert!(deframer.remainder().ends_with(tail));
+                while let Some((data, len)) = deframer.next_frame(&[], GET_FRAME_END).unwrap().map(Frame::into_parts) {
+                    emit(&data[0..len]);
+                }
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, chunk, emit| {
+                for &x in chunk {
+                    if let Some((data, len)) = deframer.push(x, GET_FRAME_END).unwrap().map(Frame::into_parts) {
+                        emit(&data[0..len]);
+                    }
+                }
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, last_end, deframer(), |deframer, mut chunk, emit| {
+                let mut out = [Frame::default(); 2];
+                loop {
+                    let count = deframer.deframe_batch(core::mem::take(&mut chunk), GET_FRAME_END, &mut out).unwrap();
+                    out[0..count].iter().for_each(|frame| emit(frame));
+                    if count == 0 {
+                        break;
+                    }
+                }
+            })?;
+            check_chunk_splits(&stream, &chunk_sizes, stream.len() / 5 * 5, Deframer::with_fixed_len(5), |deframer, mut chunk, emit| {
+                loop {
+                    let (data, len) = deframer.deframe_fixed(core::mem::take(&mut chunk)).unwrap().into_parts();
+                    if len == 0 {
+                        break;
+                    }
+                    emit(&data[0..len]);
+                }
+            })?;
+        }
+
+        #[test]
+        fn never_loses_or_duplicates_delimited_frames_across_chunk_splits(mut stream in stream::<4>(), chunk_sizes in chunk_sizes()) {
+            // Runs of delimiters are collapsed into one, so there are none in this stream
+            for i in 1..stream.len() {
+                if stream[i] == FRAME_END && stream[i - 1] == FRAME_END {
+                    stream[i] = 0x01;
+                }
+            }
+            let last_end = stream.iter().rposition(|&x| x == FRAME_END).map_or(0, |pos| pos + 1);
+            check_chunk_splits(&stream, &chunk_sizes, last_end, Deframer::with_delimiter(FRAME_END), |deframer, mut chunk, emit| {
+                while let Some((data, len)) = deframer.next_frame_delimited(core::mem::take(&mut chunk)).unwrap().map(Frame::into_parts) {
+                    emit(&data[0..len]);
+                }
+            })?;
+        }
+
+        #[test]
+        fn never_mistakes_lone_line_endings_for_the_sequence_across_chunk_splits(stream in line_stream(), chunk_sizes in chunk_sizes()) {
+            // Lone CRs and LFs in the payloads must not be mistaken for the sequence, even when
+            // they are split from the rest of it
+            let last_end = stream.windows(2).rposition(|pair| pair == b"\r\n").map_or(0, |pos| pos + 2);
+            check_chunk_splits(&stream, &chunk_sizes, last_end, Deframer::with_delimiter_seq(b"\r\n"), |deframer, mut chunk, emit| {
+                while let Some((data, len)) = deframer.next_frame_sequence(core::mem::take(&mut chunk)).unwrap().map(Frame::into_parts) {
+                    emit(&data[0..len]);
+                }
+            })?;
+        }
+    }
+
+    #[test]
+    fn leaves_the_remainder_untouched_on_an_empty_read() {
+        let mut deframer = Deframer::<4>::new();
+        assert_eq!(deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts(), ([0; 4], 0));
+        assert!(deframer.is_empty());
+
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.deframe(&[], GET_FRAME_END).unwrap().into_parts(), ([0; 4], 0));
+        assert_eq!(deframer.next_frame(&[], GET_FRAME_END).unwrap(), None);
+        assert_eq!(deframer.remainder(), [0x01, 0x02]);
+        assert_eq!(deframer.consumed(), 0);
+        assert_eq!(deframer.dropped_bytes(), 0);
+
+        // A frame already in the remainder is still returned
+        let mut deframer = Deframer::<4>::from_parts(&[0x01, FRAME_END, 0x02]).unwrap();
+        assert_eq!(*deframer.deframe(&[], GET_FRAME_END).unwrap(), [0x01, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x02]);
+    }
+
+    #[test]
+    fn ends_a_frame_on_a_frame_end_at_the_start_of_the_data() {
+        // The remainder fills all but the last element of the frame buffer
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03], GET_FRAME_END).unwrap();
+        let (data, len) = deframer.deframe(&[FRAME_END, 0x04, 0x05], GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data, [0x01, 0x02, 0x03, FRAME_END]);
+        assert_eq!(len, 4);
+        assert_eq!(deframer.remainder(), [0x04, 0x05]);
+
+        // A single element remainder
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
+        assert_eq!(*deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap(), [0x01, FRAME_END]);
+        assert!(deframer.is_empty());
+
+        // A full remainder leaves no room for the frame end
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03, 0x04], GET_FRAME_END).unwrap();
+        let result = deframer.deframe(&[FRAME_END, 0x05], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        assert_eq!(deframer.remainder(), [0x05]);
+    }
+
+    #[test]
+    fn rejects_a_full_remainder_when_strict() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03, 0x04], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder_len(), 4);
+
+        let mut deframer = Deframer::<4>::new();
+        deframer.set_strict_full_buffer(true);
+        assert_eq!(deframer.next_frame(&[0x01, 0x02, 0x03], GET_FRAME_END), Ok(None));
+        let result = deframer.deframe(&[0x04], GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 4);
+
+        // A frame that exactly fills the buffer with its frame end is still returned
+        assert_eq!(*deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END], GET_FRAME_END).unwrap(), [0x01, 0x02, 0x03, FRAME_END]);
+    }
+
+    #[test]
+    fn deframes_a_batch_into_a_caller_provided_array() {
+        let mut deframer = Deframer::<4>::new();
+        let mut out = [Frame::default(); 2];
+        let count = deframer.deframe_batch(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03, FRAME_END, 0x04], GET_FRAME_END, &mut out).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(*out[0], [0x01, FRAME_END]);
+        assert_eq!(*out[1], [0x02, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x03, FRAME_END, 0x04]);
+
+        // The frame that didn't fit is returned first
+        let count = deframer.deframe_batch(&[FRAME_END], GET_FRAME_END, &mut out).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(*out[0], [0x03, FRAME_END]);
+        assert_eq!(*out[1], [0x04, FRAME_END]);
+        assert!(deframer.is_empty());
+
+        // A failed frame is skipped, and only returned as an error if nothing else was written
+        let count = deframer.deframe_batch(&[0x01, 0x02, 0x03, 0x04, FRAME_END, 0x05, FRAME_END], GET_FRAME_END, &mut out).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(*out[0], [0x05, FRAME_END]);
+        assert_eq!(deframer.last_error().map(|context| context.error), Some(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+        let result = deframer.deframe_batch(&[0x01, 0x02, 0x03, 0x04, FRAME_END], GET_FRAME_END, &mut out);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+    }
+
+    #[test]
+    fn searches_the_data_before_each_frame_end_found_again() {
+        let mut searches = 0;
+        let mut deframer = Deframer::<8>::new();
+        deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END], |iter: &mut Iter<u8>| {
+            searches += 1;
+            iter.position(|&x| x == FRAME_END)
+        }).unwrap();
+        assert_eq!(searches, 2);
+
+        let mut searches = 0;
+        let mut deframer = Deframer::<8>::new();
+        let frame = deframer.deframe(&[0x01, FRAME_END, 0x02, FRAME_END], |iter: &mut Iter<u8>| {
+            searches += 1;
+            iter.rposition(|&x| x == FRAME_END)
+        }).unwrap();
+        assert_eq!(*frame, [0x01, FRAME_END]);
+        assert_eq!(searches, 3);
+    }
+
+    #[test]
+    fn searches_once_per_frame_end_with_a_forward_search() {
+        let mut searches = 0;
+        let mut frames = Vec::new();
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_forward_search(true);
+        deframer.deframe_each(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03], |iter: &mut Iter<u8>| {
+            searches += 1;
+            iter.position(|&x| x == FRAME_END)
+        }, |frame| frames.push(frame.to_vec())).unwrap();
+        assert_eq!(frames, [[0x01, FRAME_END], [0x02, FRAME_END]]);
+        assert_eq!(deframer.remainder(), [0x03]);
+        // One search for each frame end, and one that finds none in what follows
+        assert_eq!(searches, 3);
+    }
+}
@@ -0,0 +1,68 @@
+//! Framing where the last byte of each frame is marked by a bit within it rather than by a
+//! separate delimiter, as with 7-bit links that set the high bit of a frame's final byte
+
+use crate::{DeframeError, Deframer, Frame, FrameEnd};
+
+impl<const N: usize, const BUF: usize> Deframer<N, u8, BUF> {
+    /// Deframes data where a frame ends with the first byte that has any bit of `mask` set, such
+    /// as `0x80` for 7-bit data. That byte carries data too, so it is kept in the returned frame,
+    /// with the bits of `mask` cleared if `clear_mask` is set. It also counts towards the maximum
+    /// frame length set with [`Deframer::set_max_frame_len`].
+    pub fn deframe_masked(&mut self, data_frame: &[u8], mask: u8, clear_mask: bool) -> Result<Frame<N>, DeframeError> {
+        Ok(self.next_frame_masked(data_frame, mask, clear_mask)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_masked`], but returns `Ok(None)` while no complete frame is
+    /// available
+    pub fn next_frame_masked(&mut self, data_frame: &[u8], mask: u8, clear_mask: bool) -> Result<Option<Frame<N>>, DeframeError> {
+        let mut data: [u8; N] = [0; N];
+        let len = match self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Raw(0), |iter| iter.position(|&x| x & mask != 0))? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        if clear_mask {
+            data[len - 1] &= !mask;
+        }
+        Ok(Some(Frame::new(data, self.count_frame(len))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, Deframer, Frame};
+
+    const LAST: u8 = 0x80;
+
+    #[test]
+    fn ends_frames_on_a_marked_byte() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_masked(&[0x01, 0x02, 0x03 | LAST, 0x04], LAST, false).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02, 0x03 | LAST]);
+        assert_eq!(deframer.remainder(), [0x04]);
+
+        let (data, len) = deframer.deframe_masked(&[0x05 | LAST], LAST, true).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x04, 0x05]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn returns_single_byte_frames() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.next_frame_masked(&[0x01], LAST, true).unwrap(), None);
+        assert_eq!(deframer.next_frame_masked(&[LAST, LAST], LAST, true).unwrap().map(Frame::into_parts), Some(([0x01, 0, 0, 0, 0, 0, 0, 0], 2)));
+        assert_eq!(deframer.next_frame_masked(&[], LAST, true).unwrap().map(Frame::into_parts), Some(([0; 8], 1)));
+    }
+
+    #[test]
+    fn counts_the_marked_byte_against_the_maximum_frame_length() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_max_frame_len(Some(2));
+        assert_eq!(*deframer.deframe_masked(&[0x01, 0x02 | LAST], LAST, true).unwrap(), [0x01, 0x02]);
+        assert_eq!(deframer.deframe_masked(&[0x01, 0x02, 0x03 | LAST], LAST, true), Err(DeframeError::FrameTooLong));
+        assert!(deframer.is_empty());
+
+        // Buffered data is cut off as soon as it is longer than the maximum
+        assert_eq!(deframer.deframe_masked(&[0x01, 0x02, 0x03], LAST, true), Err(DeframeError::FrameTooLong));
+        assert!(deframer.is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+//! Demultiplexing of several logical channels sharing one link, where each frame is tagged with
+//! its channel by the element after the frame end before it
+
+use crate::{first_frame_end, DeframeError, Deframer, Element};
+
+/// Where the next element of the stream goes
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Route {
+    /// It tags the channel of the frame that follows
+    Tag,
+    /// It is part of a frame on the channel
+    Channel(usize),
+    /// It is part of a frame with an unknown tag, which is dropped
+    Discard,
+}
+
+/// Deframes a stream interleaving frames from `CH` channels, each with its own deframer, so that
+/// a partial frame buffered on one channel is never mixed with data on another.
+///
+/// Every frame starts with an element tagging its channel, which follows the frame end of the
+/// previous frame or the start of the stream, and isn't part of the frame. Frame ends just
+/// before a tag, such as a frame end starting the stream, are skipped.
+#[derive(Clone, Copy)]
+pub struct MultiDeframer<const CH: usize, const N: usize, T = u8, const BUF: usize = N> {
+    deframers: [Deframer<N, T, BUF>; CH],
+    route: Route,
+    dropped_bytes: usize,
+}
+
+impl<const CH: usize, const N: usize, T: Element, const BUF: usize> MultiDeframer<CH, N, T, BUF> {
+    /// Creates a deframer for `CH` channels, each starting out empty
+    pub fn new() -> Self {
+        Self::with_deframer(Deframer::new())
+    }
+
+    /// Like [`MultiDeframer::new`], but each channel deframes with a copy of an already
+    /// configured `deframer`
+    pub fn with_deframer(deframer: Deframer<N, T, BUF>) -> Self {
+        Self { deframers: [deframer; CH], route: Route::Tag, dropped_bytes: 0 }
+    }
+
+    /// Returns the deframer of `channel`, for example to inspect its remainder
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` isn't less than `CH`.
+    pub fn deframer(&self, channel: usize) -> &Deframer<N, T, BUF> {
+        &self.deframers[channel]
+    }
+
+    /// Returns the deframer of `channel`, for example to configure it differently from the
+    /// other channels
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` isn't less than `CH`.
+    pub fn deframer_mut(&mut self, channel: usize) -> &mut Deframer<N, T, BUF> {
+        &mut self.deframers[channel]
+    }
+
+    /// Returns the number of elements dropped while routing, which are the tags and frames of
+    /// unknown channels and the frames whose frame end couldn't be found. Frames that fail on
+    /// their channel are counted in the [`Deframer::dropped_bytes`] of that channel instead.
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    /// Discards the remainders of all channels, so that the next element is taken as a tag
+    pub fn reset(&mut self) {
+        self.deframers.iter_mut().for_each(Deframer::reset);
+        self.route = Route::Tag;
+    }
+
+    /// Routes the frames in `data_frame` to the deframers of their channels, and calls `f` with
+    /// the channel and elements of every frame completed, as with [`Deframer::deframe_each`].
+    /// `get_channel` returns the channel for a tag, and `get_frame_end` finds the frame ends of
+    /// every channel.
+    ///
+    /// The channel a frame is routed to is only decided once its frame end is found, so a frame
+    /// end must be found in a single data frame, such as a single element delimiter.
+    ///
+    /// A tag for which `get_channel` returns `None` or a channel that isn't less than `CH` is an
+    /// [`DeframeError::InvalidEncoding`] error, and its frame is dropped. If `get_frame_end`
+    /// fails on a frame, the frame is dropped along with whatever its channel had buffered, up to
+    /// the next element that is a frame end on its own. Frames that fail on their channel are
+    /// dropped as with [`Deframer::deframe_each`]. Either way deframing continues with the
+    /// following frame, and the first error is returned once all of `data_frame` is processed.
+    pub fn deframe_each(&mut self, data_frame: &[T], mut get_channel: impl FnMut(T) -> Option<usize>, mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, mut f: impl FnMut(usize, &[T])) -> Result<(), DeframeError> {
+        let mut data_frame = data_frame;
+        let mut result = Ok(());
+        while let Some((&first, rest)) = data_frame.split_first() {
+            if self.route == Route::Tag {
+                let channel = match first_frame_end(&data_frame[0..1], false, &mut get_frame_end) {
+                    Ok(Some(0)) => None,
+                    Ok(_) => Some(get_channel(first).filter(|&channel| channel < CH).ok_or(DeframeError::InvalidEncoding)),
+                    Err(error) => Some(Err(error)),
+                };
+                match channel {
+                    Some(Ok(channel)) => self.route = Route::Channel(channel),
+                    Some(Err(error)) => {
+                        result = result.and(Err(error));
+                        self.dropped_bytes = self.dropped_bytes.wrapping_add(1);
+                        self.route = Route::Discard;
+                    }
+                    None => {}
+                }
+                data_frame = rest;
+                continue;
+            }
+
+            let forward = matches!(self.route, Route::Channel(channel) if self.deframers[channel].forward_search);
+            let end = match first_frame_end(data_frame, forward, &mut get_frame_end) {
+                Ok(end) => end,
+                Err(error) => {
+                    result = result.and(Err(error));
+                    if let Route::Channel(channel) = self.route {
+                        self.dropped_bytes = self.dropped_bytes.wrapping_add(self.deframers[channel].remainder_len());
+                        self.deframers[channel].reset();
+                    }
+                    self.route = Route::Discard;
+                    (0..data_frame.len()).find(|&i| first_frame_end(&data_frame[i..i + 1], false, &mut get_frame_end) == Ok(Some(0)))
+                }
+            };
+            let (frame, next) = data_frame.split_at(end.map_or(data_frame.len(), |end| end + 1));
+            match self.route {
+                Route::Channel(channel) => result = result.and(self.deframers[channel].deframe_each(frame, &mut get_frame_end, |frame| f(channel, frame))),
+                _ => self.dropped_bytes = self.dropped_bytes.wrapping_add(frame.len()),
+            }
+            if end.is_some() {
+                self.route = Route::Tag;
+            }
+            data_frame = next;
+        }
+        result
+    }
+}
+
+impl<const CH: usize, const N: usize, T: Element, const BUF: usize> Default for MultiDeframer<CH, N, T, BUF> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use super::MultiDeframer;
+    use crate::DeframeError;
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+    const GET_CHANNEL: fn(tag: u8) -> Option<usize> = |tag| tag.checked_sub(b'A').map(usize::from);
+
+    #[test]
+    fn routes_frames_to_their_channels() {
+        let mut deframer = MultiDeframer::<2, 8>::new();
+        let mut frames = [(0, [0; 8], 0); 4];
+        let mut count = 0;
+        let mut record = |channel: usize, frame: &[u8]| {
+            frames[count].0 = channel;
+            frames[count].1[0..frame.len()].copy_from_slice(frame);
+            frames[count].2 = frame.len();
+            count += 1;
+        };
+
+        deframer.deframe_each(&[FRAME_END, b'A', 0x01, FRAME_END, b'B', 0x02], GET_CHANNEL, GET_FRAME_END, &mut record).unwrap();
+        assert_eq!(deframer.deframer(1).remainder(), [0x02]);
+        assert!(deframer.deframer(0).is_empty());
+
+        // The partial frame on channel B is completed in the next data frame
+        deframer.deframe_each(&[0x03, FRAME_END, b'A', FRAME_END], GET_CHANNEL, GET_FRAME_END, &mut record).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!((frames[0].0, &frames[0].1[0..frames[0].2]), (0, &[0x01, FRAME_END][..]));
+        assert_eq!((frames[1].0, &frames[1].1[0..frames[1].2]), (1, &[0x02, 0x03, FRAME_END][..]));
+        assert_eq!((frames[2].0, &frames[2].1[0..frames[2].2]), (0, &[FRAME_END][..]));
+    }
+
+    #[test]
+    fn drops_frames_with_an_unknown_tag() {
+        let mut deframer = MultiDeframer::<2, 8>::new();
+        let mut channels = [0; 2];
+        let result = deframer.deframe_each(&[b'C', 0x01, FRAME_END, b'B', 0x02, FRAME_END], GET_CHANNEL, GET_FRAME_END, |channel, _| channels[channel] += 1);
+        assert_eq!(result, Err(DeframeError::InvalidEncoding));
+        assert_eq!(channels, [0, 1]);
+        assert_eq!(deframer.dropped_bytes(), 3);
+
+        // Each channel keeps its own partial frame until reset
+        deframer.deframe_each(&[b'A', 0x03], GET_CHANNEL, GET_FRAME_END, |_, _| {}).unwrap();
+        assert_eq!(deframer.deframer(0).remainder(), [0x03]);
+        deframer.reset();
+        assert!(deframer.deframer(0).is_empty());
+    }
+
+    #[test]
+    fn keeps_routing_after_a_failed_frame_end_search() {
+        let mut deframer = MultiDeframer::<2, 8>::new();
+        let mut frames = [(0, 0); 2];
+        let mut count = 0;
+        // Reports a frame end past the data when searching from a 0xFF
+        let get_frame_end = |iter: &mut Iter<u8>| if iter.as_slice().first() == Some(&0xFF) { Some(usize::MAX) } else { GET_FRAME_END(iter) };
+        let result = deframer.deframe_each(&[b'A', 0x01, FRAME_END, b'B', 0xFF, 0x02, FRAME_END, b'A', 0x03, FRAME_END], GET_CHANNEL, get_frame_end, |channel, frame| {
+            frames[count] = (channel, frame[0]);
+            count += 1;
+        });
+        assert_eq!(result, Err(DeframeError::InvalidFrameEnd));
+        assert_eq!(frames[0..count], [(0, 0x01), (0, 0x03)]);
+        assert_eq!(deframer.dropped_bytes(), 3);
+    }
+}
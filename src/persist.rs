@@ -0,0 +1,80 @@
+//! Serde support for persisting the remainder of a deframer, such as to flash across a reset
+
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Deframer, Element};
+
+impl<const N: usize, T: Element + Serialize, const BUF: usize> Serialize for Deframer<N, T, BUF> {
+    /// Serializes only the valid part of the remainder, as a sequence whose length is the
+    /// remainder length. The configuration and counters aren't serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.remainder().serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize, T: Element + Deserialize<'de>, const BUF: usize> Deserialize<'de> for Deframer<N, T, BUF> {
+    /// Deserializes a remainder into a deframer as with [`Deframer::from_parts`], which is
+    /// otherwise freshly constructed, so any configuration must be set again. A remainder longer
+    /// than the remainder capacity `BUF` is an error.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(RemainderVisitor(PhantomData))
+    }
+}
+
+/// Reads a serialized remainder into a deframer
+struct RemainderVisitor<const N: usize, T, const BUF: usize>(PhantomData<T>);
+
+impl<'de, const N: usize, T: Element + Deserialize<'de>, const BUF: usize> Visitor<'de> for RemainderVisitor<N, T, BUF> {
+    type Value = Deframer<N, T, BUF>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a remainder of at most {} elements", BUF)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut remainder = [T::ZERO; BUF];
+        let mut len = 0;
+        while let Some(element) = seq.next_element()? {
+            if len == BUF {
+                return Err(A::Error::invalid_length(len + 1, &self));
+            }
+            remainder[len] = element;
+            len += 1;
+        }
+        Deframer::from_parts(&remainder[0..len]).map_err(|_| A::Error::invalid_length(len, &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    use crate::Deframer;
+
+    #[test]
+    fn round_trips_the_remainder() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, 0x0A, 0x02, 0x03], |iter| iter.position(|&x| x == 0x0A)).unwrap();
+        assert_tokens(&deframer, &[Token::Seq { len: Some(2) }, Token::U8(0x02), Token::U8(0x03), Token::SeqEnd]);
+        assert_tokens(&Deframer::<4>::new(), &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
+    }
+
+    #[test]
+    fn rejects_a_remainder_longer_than_the_capacity() {
+        assert_de_tokens_error::<Deframer<2>>(
+            &[Token::Seq { len: Some(3) }, Token::U8(0x01), Token::U8(0x02), Token::U8(0x03), Token::SeqEnd],
+            "invalid length 3, expected a remainder of at most 2 elements",
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_remainders() {
+        assert_de_tokens_error::<Deframer<2>>(&[Token::Bool(true)], "invalid type: boolean `true`, expected a remainder of at most 2 elements");
+        assert_de_tokens_error::<Deframer<2>>(&[Token::Seq { len: Some(1) }, Token::U16(0x100), Token::SeqEnd], "invalid value: integer `256`, expected u8");
+    }
+}
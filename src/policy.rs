@@ -0,0 +1,114 @@
+//! Where the frame end of each frame ends up: kept, stripped, or moved to the next frame
+
+use crate::{strip_frame_end, DeframeError, Deframer, Element, Overflow, MAX_DELIMITER_SEQ_LEN};
+
+/// Where the frame end of each frame ends up in the returned frames
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DelimiterPolicy {
+    /// The frame end is kept at the end of its frame
+    #[default]
+    Include,
+    /// The frame end is removed, as with [`Deframer::set_strip_delimiter`]
+    Strip,
+    /// The frame end is moved to the start of the frame that follows it, for framing where a
+    /// marker starts each frame rather than ending it. The first frame has no marker to start
+    /// it, so it is returned as is.
+    PrefixNext,
+}
+
+/// The frame end held back by [`DelimiterPolicy::PrefixNext`] to start the next frame
+#[derive(Clone, Copy)]
+pub(crate) struct CarriedEnd<T> {
+    elements: [T; MAX_DELIMITER_SEQ_LEN],
+    len: usize,
+}
+
+impl<T: Element> CarriedEnd<T> {
+    pub(crate) const fn new() -> Self {
+        Self { elements: [T::ZERO; MAX_DELIMITER_SEQ_LEN], len: 0 }
+    }
+}
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Sets where the frame end of each frame ends up, see [`DelimiterPolicy`]. This applies to
+    /// the same methods as [`Deframer::set_strip_delimiter`], and frame ends are included by
+    /// default.
+    pub fn set_delimiter_policy(&mut self, delimiter_policy: DelimiterPolicy) {
+        self.delimiter_policy = delimiter_policy;
+        self.carried_end = CarriedEnd::new();
+    }
+}
+
+/// Places the `end_len` long frame end of a frame of length `len` in `out` according to
+/// `policy`, returning the final frame length. A frame end carried over from the previous frame
+/// that doesn't fit in front of the frame is an [`Overflow::SingleFrameTooLarge`] error.
+pub(crate) fn place_frame_end<T: Element>(out: &mut [T], len: usize, end_len: usize, policy: DelimiterPolicy, carried_end: &mut CarriedEnd<T>) -> Result<usize, DeframeError> {
+    match policy {
+        DelimiterPolicy::Include => Ok(len),
+        DelimiterPolicy::Strip => Ok(strip_frame_end(out, len, end_len)),
+        DelimiterPolicy::PrefixNext => {
+            // [carried end...payload], with this frame's end carried to the next
+            let carried = core::mem::replace(carried_end, CarriedEnd::new());
+            carried_end.elements[0..end_len].copy_from_slice(&out[len - end_len..len]);
+            carried_end.len = end_len;
+            let payload_len = strip_frame_end(out, len, end_len);
+            let prefixed_len = carried.len + payload_len;
+            if prefixed_len > out.len() {
+                out[0..payload_len].fill(T::ZERO);
+                return Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge));
+            }
+            out.copy_within(0..payload_len, carried.len);
+            out[0..carried.len].copy_from_slice(&carried.elements[0..carried.len]);
+            Ok(prefixed_len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use super::DelimiterPolicy;
+    use crate::{Deframer, DeframerBuilder};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn includes_or_strips_the_frame_end() {
+        let mut deframer = DeframerBuilder::<8>::new().delimiter_policy(DelimiterPolicy::Include).build();
+        assert_eq!(*deframer.deframe(&[0x01, FRAME_END], GET_FRAME_END).unwrap(), [0x01, FRAME_END]);
+
+        deframer.set_delimiter_policy(DelimiterPolicy::Strip);
+        assert_eq!(*deframer.deframe(&[0x02, FRAME_END], GET_FRAME_END).unwrap(), [0x02]);
+    }
+
+    #[test]
+    fn prefixes_the_next_frame_with_the_frame_end() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_delimiter_policy(DelimiterPolicy::PrefixNext);
+        let data = [0x01, FRAME_END, 0x02, 0x03, FRAME_END, 0x04, FRAME_END];
+        assert_eq!(*deframer.deframe(&data, GET_FRAME_END).unwrap(), [0x01]);
+        assert_eq!(*deframer.deframe(&[], GET_FRAME_END).unwrap(), [FRAME_END, 0x02, 0x03]);
+        assert_eq!(*deframer.deframe(&[], GET_FRAME_END).unwrap(), [FRAME_END, 0x04]);
+
+        let mut deframer = Deframer::<16>::with_delimiter_seq(b"\r\n");
+        deframer.set_delimiter_policy(DelimiterPolicy::PrefixNext);
+        let (data, len) = deframer.deframe_sequence(b"ab\r\ncd\r").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"ab");
+        let (data, len) = deframer.deframe_sequence(b"\n").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"\r\ncd");
+    }
+
+    #[test]
+    fn forgets_the_carried_frame_end_on_reset() {
+        let mut deframer = Deframer::<3>::new();
+        deframer.set_delimiter_policy(DelimiterPolicy::PrefixNext);
+        assert_eq!(*deframer.deframe(&[0x01, FRAME_END], GET_FRAME_END).unwrap(), [0x01]);
+        assert_eq!(*deframer.deframe(&[0x02, 0x03, FRAME_END], GET_FRAME_END).unwrap(), [FRAME_END, 0x02, 0x03]);
+
+        deframer.reset();
+        assert_eq!(*deframer.deframe(&[0x04, FRAME_END], GET_FRAME_END).unwrap(), [0x04]);
+    }
+}
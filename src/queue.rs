@@ -0,0 +1,167 @@
+//! A fixed capacity queue of deframed frames, for deframing data as it arrives and processing
+//! the frames later
+
+use crate::{DeframeError, Deframer, Element, Frame};
+
+/// An error from queueing frames
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum QueueError {
+    /// The data couldn't be deframed
+    Deframe(DeframeError),
+    /// The queue was full while a complete frame was waiting to be queued. The frame is still
+    /// buffered in the remainder, so nothing is lost as long as frames are popped before the
+    /// remainder fills.
+    Full,
+}
+
+/// Deframes data into a ring of up to `Q` frames, which are taken out in order with
+/// [`DeframerQueue::pop_frame`].
+///
+/// Both [`DeframerQueue::deframe`] and [`DeframerQueue::pop_frame`] take `&mut self`, so the
+/// queue isn't split into a producer and a consumer that can be used from different contexts at
+/// once. To feed it from an interrupt handler and drain it elsewhere, share it behind a mutex,
+/// such as a critical section, and hold the lock only for each call. Popping a frame is a short
+/// copy, so the interrupt is delayed for little longer than that.
+pub struct DeframerQueue<const N: usize, const Q: usize, T = u8, const BUF: usize = N, F = fn(&mut core::slice::Iter<T>) -> Option<usize>> {
+    deframer: Deframer<N, T, BUF>,
+    get_frame_end: F,
+    frames: [Frame<N, T>; Q],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize, const Q: usize, T: Element, const BUF: usize, F: FnMut(&mut core::slice::Iter<T>) -> Option<usize>> DeframerQueue<N, Q, T, BUF, F> {
+    /// Creates an empty queue, splitting data into frames with `get_frame_end` as with
+    /// [`Deframer::deframe`]
+    pub fn new(get_frame_end: F) -> Self {
+        Self::with_deframer(Deframer::new(), get_frame_end)
+    }
+
+    /// Like [`DeframerQueue::new`], but deframes with an already configured `deframer`
+    pub fn with_deframer(deframer: Deframer<N, T, BUF>, get_frame_end: F) -> Self {
+        const { assert!(Q > 0, "frame queue capacity Q of zero") };
+        Self {
+            deframer,
+            get_frame_end,
+            frames: [Frame::default(); Q],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the deframer, for example to inspect its remainder
+    pub fn deframer(&self) -> &Deframer<N, T, BUF> {
+        &self.deframer
+    }
+
+    /// Deframes `data_frame`, queueing each complete frame, and returns how many were queued.
+    ///
+    /// Frames that fail are dropped as with [`Deframer::deframe_batch`], and the first such error
+    /// is returned if no frame was queued. Once the queue is full the rest of the data is
+    /// buffered in the remainder, and if it holds a complete frame that didn't fit,
+    /// [`QueueError::Full`] is returned. No frame is lost then, as those left in the remainder
+    /// are queued by a later call once frames are popped, which may be given no data.
+    pub fn deframe(&mut self, data_frame: &[T]) -> Result<usize, QueueError> {
+        let mut data_frame = data_frame;
+        let mut queued = 0;
+        // The free slots wrap around the end of the ring, so are filled in up to two runs, with
+        // only the first given the data
+        loop {
+            let tail = (self.head + self.len) % Q;
+            let run = (Q - tail).min(Q - self.len);
+            let count = self.deframer.deframe_batch(core::mem::take(&mut data_frame), &mut self.get_frame_end, &mut self.frames[tail..tail + run]);
+            match count {
+                Ok(count) => {
+                    self.len += count;
+                    queued += count;
+                    if count < run || self.len == Q {
+                        break;
+                    }
+                }
+                Err(error) if queued == 0 => return Err(QueueError::Deframe(error)),
+                Err(_) => break,
+            }
+        }
+
+        if self.len == Q && self.deframer.count_frames(&mut self.get_frame_end) > 0 {
+            return Err(QueueError::Full);
+        }
+        Ok(queued)
+    }
+
+    /// Takes the oldest queued frame, or returns `None` if the queue is empty
+    pub fn pop_frame(&mut self) -> Option<Frame<N, T>> {
+        if self.len == 0 {
+            return None;
+        }
+        let frame = core::mem::take(&mut self.frames[self.head]);
+        self.head = (self.head + 1) % Q;
+        self.len -= 1;
+        Some(frame)
+    }
+
+    /// Returns the number of queued frames
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no frames are queued
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if no more frames can be queued until one is popped
+    pub fn is_full(&self) -> bool {
+        self.len == Q
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use super::{DeframerQueue, QueueError};
+    use crate::{DeframeError, Overflow};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn queues_frames_in_order() {
+        let mut queue = DeframerQueue::<4, 3>::new(GET_FRAME_END);
+        assert_eq!(queue.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03]), Ok(2));
+        assert_eq!(*queue.pop_frame().unwrap(), [0x01, FRAME_END]);
+
+        // The free slots wrap around the end of the ring
+        assert_eq!(queue.deframe(&[FRAME_END, 0x04, FRAME_END]), Ok(2));
+        assert!(queue.is_full());
+        assert_eq!(*queue.pop_frame().unwrap(), [0x02, FRAME_END]);
+        assert_eq!(*queue.pop_frame().unwrap(), [0x03, FRAME_END]);
+        assert_eq!(*queue.pop_frame().unwrap(), [0x04, FRAME_END]);
+        assert!(queue.pop_frame().is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn keeps_frames_that_overflow_the_queue_buffered() {
+        let mut queue = DeframerQueue::<4, 2, u8, 16>::new(GET_FRAME_END);
+        assert_eq!(queue.deframe(&[0x01, FRAME_END, 0x02, FRAME_END, 0x03, FRAME_END, 0x04]), Err(QueueError::Full));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.deframer().remainder(), [0x03, FRAME_END, 0x04]);
+
+        // A full queue with only a partial frame buffered hasn't overflowed
+        assert_eq!(*queue.pop_frame().unwrap(), [0x01, FRAME_END]);
+        assert_eq!(queue.deframe(&[]), Ok(1));
+        assert_eq!(queue.deframer().remainder(), [0x04]);
+        assert_eq!(*queue.pop_frame().unwrap(), [0x02, FRAME_END]);
+        assert_eq!(*queue.pop_frame().unwrap(), [0x03, FRAME_END]);
+    }
+
+    #[test]
+    fn returns_errors_when_no_frame_was_queued() {
+        let mut queue = DeframerQueue::<2, 2>::new(GET_FRAME_END);
+        assert_eq!(queue.deframe(&[0x01, 0x02, 0x03, FRAME_END]), Err(QueueError::Deframe(DeframeError::Overflow(Overflow::SingleFrameTooLarge))));
+        assert!(queue.is_empty());
+    }
+}
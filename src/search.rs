@@ -0,0 +1,114 @@
+//! Frame end searches, for use as the `get_frame_end` of [`Deframer::deframe`] or in other
+//! buffering code
+//!
+//! [`Deframer::deframe`]: crate::Deframer::deframe
+
+/// Returns the position of the first `delimiter` in `data`. To deframe on it, pass
+/// `|iter| find_frame_end(iter.as_slice(), delimiter)` as the `get_frame_end` of
+/// [`Deframer::deframe`].
+///
+/// [`Deframer::deframe`]: crate::Deframer::deframe
+pub fn find_frame_end<T: PartialEq>(data: &[T], delimiter: T) -> Option<usize> {
+    data.iter().position(|x| *x == delimiter)
+}
+
+/// Returns a `get_frame_end` for [`Deframer::deframe`] and the methods like it that ends frames
+/// on `delimiter`, searching forward for the first one as [`Iterator::position`] does.
+///
+/// A callback written with [`Iterator::rposition`] instead finds the last delimiter, but returns
+/// the same frames in the same order, as the deframer then searches again before it until it
+/// reaches the first one. It only makes data holding several frames slower to deframe, as that
+/// data is searched more than once. With a forward search like this one, enabling
+/// [`Deframer::set_forward_search`] skips searching again altogether.
+///
+/// [`Deframer::deframe`]: crate::Deframer::deframe
+/// [`Deframer::set_forward_search`]: crate::Deframer::set_forward_search
+pub fn search_frame_end<T: Copy + PartialEq>(delimiter: T) -> impl FnMut(&mut core::slice::Iter<T>) -> Option<usize> {
+    move |iter| find_frame_end(iter.as_slice(), delimiter)
+}
+
+/// Returns the position of the last element of the first whole `delimiter_seq` in `data`, which
+/// is where a frame ending with the sequence ends. An empty sequence is never found.
+///
+/// A sequence split across two calls isn't matched, see [`Deframer::deframe_sequence`] for that.
+///
+/// [`Deframer::deframe_sequence`]: crate::Deframer::deframe_sequence
+pub fn find_sequence_end<T: PartialEq>(data: &[T], delimiter_seq: &[T]) -> Option<usize> {
+    if delimiter_seq.is_empty() {
+        return None;
+    }
+    data.windows(delimiter_seq.len()).position(|window| window == delimiter_seq).map(|start| start + delimiter_seq.len() - 1)
+}
+
+/// Iterates over the frames of a complete message in `data`, split on `delimiter` and borrowed
+/// without the delimiter. Nothing is buffered, so the trailing segment after the last delimiter
+/// is returned as a frame too, unless it is empty.
+pub fn frames_in<T: Copy + PartialEq>(data: &[T], delimiter: T) -> impl Iterator<Item = &[T]> {
+    data.split_inclusive(move |x| *x == delimiter).map(move |frame| match frame.split_last() {
+        Some((last, payload)) if *last == delimiter => payload,
+        _ => frame,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::slice::Iter;
+
+    use super::{find_frame_end, find_sequence_end, frames_in, search_frame_end};
+    use crate::Deframer;
+
+    #[test]
+    fn finds_the_first_delimiter() {
+        assert_eq!(find_frame_end(&[0x01, 0x0A, 0x02, 0x0A], 0x0A), Some(1));
+        assert_eq!(find_frame_end(&[0x01, 0x02], 0x0A), None);
+
+        let mut deframer = Deframer::<8>::new();
+        let frame = deframer.deframe(&[0x01, 0x0A, 0x02], |iter| find_frame_end(iter.as_slice(), 0x0A)).unwrap();
+        assert_eq!(*frame, [0x01, 0x0A]);
+    }
+
+    /// Deframes the same data with `get_frame_end`, returning the frames and what is left buffered
+    fn deframe_with(mut get_frame_end: impl FnMut(&mut Iter<u8>) -> Option<usize>) -> (Vec<Vec<u8>>, Vec<u8>) {
+        let mut deframer = Deframer::<8>::new();
+        let mut frames = Vec::new();
+        for data_frame in [&[0x01, 0x0A, 0x02, 0x0A, 0x03, 0x0A, 0x04][..], &[], &[0x05, 0x0A], &[], &[0x06]] {
+            frames.extend(deframer.next_frame(data_frame, &mut get_frame_end).unwrap().map(|frame| frame.to_vec()));
+        }
+        (frames, deframer.remainder().to_vec())
+    }
+
+    #[test]
+    fn returns_the_same_frames_as_a_reverse_search() {
+        let (frames, remainder) = deframe_with(search_frame_end(0x0A));
+        assert_eq!(frames, [vec![0x01, 0x0A], vec![0x02, 0x0A], vec![0x03, 0x0A], vec![0x04, 0x05, 0x0A]]);
+        assert_eq!(remainder, [0x06]);
+        assert_eq!(deframe_with(|iter| iter.rposition(|&x| x == 0x0A)), (frames, remainder));
+    }
+
+    #[test]
+    fn finds_the_end_of_the_first_sequence() {
+        assert_eq!(find_sequence_end(b"ab\r\ncd\r\n", b"\r\n"), Some(3));
+        assert_eq!(find_sequence_end(b"\r\r\n", b"\r\n"), Some(2));
+        assert_eq!(find_sequence_end(b"ab\r", b"\r\n"), None);
+        assert_eq!(find_sequence_end(b"ab", b""), None);
+
+        let mut deframer = Deframer::<8>::new();
+        let frame = deframer.deframe(b"ab\r\ncd", |iter| find_sequence_end(iter.as_slice(), b"\r\n")).unwrap();
+        assert_eq!(*frame, *b"ab\r\n");
+    }
+
+    #[test]
+    fn iterates_over_the_frames_in_a_slice() {
+        let mut frames = frames_in(&[0x01, 0x0A, 0x0A, 0x02, 0x03, 0x0A, 0x04], 0x0A);
+        assert_eq!(frames.next(), Some(&[0x01][..]));
+        assert_eq!(frames.next(), Some(&[][..]));
+        assert_eq!(frames.next(), Some(&[0x02, 0x03][..]));
+        assert_eq!(frames.next(), Some(&[0x04][..]));
+        assert_eq!(frames.next(), None);
+
+        assert_eq!(frames_in(&[0x01, 0x0A], 0x0A).count(), 1);
+        assert_eq!(frames_in(&[], 0x0A).count(), 0);
+    }
+}
@@ -0,0 +1,191 @@
+//! Framing on a multi-element delimiter sequence, such as a sync word, which may be split across
+//! data frames
+
+use crate::{finish_frame, DeframeError, Deframer, Element, Frame, FrameEnd, MAX_DELIMITER_SEQ_LEN};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Creates a deframer that ends frames on the sequence `delimiter_seq` when using
+    /// [`Deframer::deframe_sequence`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequence is empty or longer than [`MAX_DELIMITER_SEQ_LEN`].
+    pub const fn with_delimiter_seq(delimiter_seq: &[T]) -> Self {
+        assert!(!delimiter_seq.is_empty(), "empty delimiter sequence");
+        assert!(delimiter_seq.len() <= MAX_DELIMITER_SEQ_LEN, "delimiter sequence too long");
+        let mut deframer = Self::new();
+        let mut i = 0;
+        while i < delimiter_seq.len() {
+            deframer.delimiter_seq[i] = delimiter_seq[i];
+            i += 1;
+        }
+        deframer.delimiter_seq_len = delimiter_seq.len();
+        deframer
+    }
+
+    /// Deframes `data_frame` on the delimiter sequence the deframer was constructed with, so that
+    /// each frame ends with the whole sequence. A sequence that starts in the remainder and ends
+    /// in `data_frame` is still matched, however many data frames it was split across, as the
+    /// partial sequence stays at the end of the remainder until it is completed.
+    ///
+    /// If stripping is enabled the whole sequence is stripped, and a checksum is expected just
+    /// before the sequence.
+    ///
+    /// Returns `Err(DeframeError::NotConfigured)`, leaving `data_frame` unbuffered, if the
+    /// deframer wasn't constructed with [`Deframer::with_delimiter_seq`].
+    pub fn deframe_sequence(&mut self, data_frame: &[T]) -> Result<Frame<N, T>, DeframeError> {
+        Ok(self.next_frame_sequence(data_frame)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_sequence`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a sequence stripped down to an empty frame can be told apart
+    pub fn next_frame_sequence(&mut self, data_frame: &[T]) -> Result<Option<Frame<N, T>>, DeframeError> {
+        let seq_len = self.delimiter_seq_len;
+        if seq_len == 0 {
+            return Err(DeframeError::NotConfigured);
+        }
+        let delimiter_seq = self.delimiter_seq;
+        let delimiter_seq = &delimiter_seq[0..seq_len];
+
+        // The end of the remainder, which may hold the start of a sequence ending in the data
+        let mut tail = [T::ZERO; MAX_DELIMITER_SEQ_LEN];
+        let tail_len = self.remainder_length.min(seq_len - 1);
+        tail[0..tail_len].copy_from_slice(&self.remainder[self.remainder_length - tail_len..self.remainder_length]);
+        let tail = &tail[0..tail_len];
+
+        // Only the data immediately follows the tail, the remainder is searched on its own so that
+        // its end isn't matched against itself
+        let data_start = data_frame.as_ptr();
+        let mut data: [T; N] = [T::ZERO; N];
+        let len = self.deframe_frame(data_frame, Some(&mut data), FrameEnd::Raw(seq_len), |iter| {
+            let data = iter.as_slice();
+            let tail = if data.as_ptr() == data_start { tail } else { &[] };
+            (0..data.len()).find(|&end| sequence_ends_at(tail, data, end, delimiter_seq))
+        })?;
+
+        let len = match len {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        match finish_frame(&mut data, len, seq_len, self.checksum, self.transform, self.delimiter_policy, &mut self.carried_end) {
+            Ok(len) => Ok(Some(Frame::new(data, self.count_frame(len)))),
+            Err(error) => Err(self.drop_frame(len, error)),
+        }
+    }
+}
+
+/// Returns true if `delimiter_seq` ends at `data[end]`, where the sequence may start in `tail`
+/// which immediately precedes `data`
+fn sequence_ends_at<T: Element>(tail: &[T], data: &[T], end: usize, delimiter_seq: &[T]) -> bool {
+    let seq_len = delimiter_seq.len();
+    if end + 1 + tail.len() < seq_len {
+        return false;
+    }
+    delimiter_seq.iter().rev().enumerate().all(|(back, x)| {
+        if back <= end {
+            data[end - back] == *x
+        } else {
+            tail[tail.len() + end - back] == *x
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, Deframer, Frame};
+
+    const SYNC: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];
+
+    #[test]
+    fn deframes_on_a_delimiter_sequence() {
+        let mut deframer = Deframer::<16>::with_delimiter_seq(&SYNC);
+        let (data, len) = deframer.deframe_sequence(&[0x01, 0xDE, 0xAD, 0xBE, 0xEF, 0x02, 0xDE]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(deframer.remainder(), [0x02, 0xDE]);
+
+        // A partial sequence on its own doesn't end a frame
+        let (_data, len) = deframer.deframe_sequence(&[0xAD, 0xBE, 0x03]).unwrap().into_parts();
+        assert_eq!(len, 0);
+    }
+
+    #[test]
+    fn matches_a_sequence_split_across_data_frames() {
+        let mut deframer = Deframer::<16>::with_delimiter_seq(&SYNC);
+        deframer.set_strip_delimiter(true);
+
+        let (_data, len) = deframer.deframe_sequence(&[0x01, 0x02, 0xDE, 0xAD]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (_data, len) = deframer.deframe_sequence(&[0xBE]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (data, len) = deframer.deframe_sequence(&[0xEF, 0x03]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0x02]);
+        assert_eq!(deframer.remainder(), [0x03]);
+    }
+
+    #[test]
+    fn matches_a_sequence_split_one_element_per_call() {
+        let mut deframer = Deframer::<16>::with_delimiter_seq(&SYNC);
+        deframer.set_strip_delimiter(true);
+
+        // A false start followed by the whole sequence, one element at a time
+        for &x in &[0x01, 0xDE, 0xAD, 0xDE, 0xAD, 0xBE] {
+            assert_eq!(deframer.next_frame_sequence(&[x]).unwrap(), None);
+        }
+        let (data, len) = deframer.deframe_sequence(&[0xEF]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01, 0xDE, 0xAD]);
+        assert!(deframer.is_empty());
+
+        for &x in &SYNC[0..3] {
+            assert_eq!(deframer.next_frame_sequence(&[x]).unwrap(), None);
+        }
+        assert_eq!(deframer.next_frame_sequence(&[0xEF]).unwrap().map(Frame::into_parts), Some(([0; 16], 0)));
+    }
+
+    #[test]
+    fn does_not_match_the_remainder_against_its_own_end() {
+        let mut deframer = Deframer::<16>::with_delimiter_seq(b"\r\n");
+        assert_eq!(deframer.next_frame_sequence(b"\n\r").unwrap(), None);
+        assert_eq!(deframer.next_frame_sequence(&[]).unwrap(), None);
+        assert_eq!(deframer.remainder(), b"\n\r");
+
+        let (data, len) = deframer.deframe_sequence(b"\n").unwrap().into_parts();
+        assert_eq!(data[0..len], *b"\n\r\n");
+    }
+
+    #[test]
+    fn drains_buffered_frames_before_matching_the_tail() {
+        let mut deframer = Deframer::<16>::with_delimiter_seq(&SYNC);
+        deframer.set_strip_delimiter(true);
+
+        let (data, len) = deframer.deframe_sequence(&[0x01, 0xDE, 0xAD, 0xBE, 0xEF, 0x02, 0xDE, 0xAD, 0xBE, 0xEF, 0xDE, 0xAD]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x01]);
+        let (data, len) = deframer.deframe_sequence(&[0xBE]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x02]);
+
+        // The rest of the buffered sequence completes in the next data frame
+        assert_eq!(deframer.next_frame_sequence(&[0xEF]).unwrap().map(Frame::into_parts), Some(([0; 16], 0)));
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn does_not_count_the_sequence_against_the_maximum_frame_length() {
+        let mut deframer = Deframer::<8>::with_delimiter_seq(b"\r\n");
+        deframer.set_max_frame_len(Some(2));
+        assert_eq!(*deframer.deframe_sequence(b"ab\r\n").unwrap(), *b"ab\r\n");
+
+        // Nor the start of a sequence that is still buffered
+        assert_eq!(deframer.next_frame_sequence(b"cd\r").unwrap(), None);
+        assert_eq!(*deframer.deframe_sequence(b"\n").unwrap(), *b"cd\r\n");
+
+        assert_eq!(deframer.deframe_sequence(b"abc\r\n"), Err(DeframeError::FrameTooLong));
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn rejects_a_deframer_without_a_delimiter_sequence() {
+        let mut deframer = Deframer::<8>::new();
+        assert_eq!(deframer.deframe_sequence(b"x"), Err(DeframeError::NotConfigured));
+        assert_eq!(deframer.next_frame_sequence(b"\r\n"), Err(DeframeError::NotConfigured));
+        assert!(deframer.is_empty());
+    }
+}
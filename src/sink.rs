@@ -0,0 +1,53 @@
+//! Delivery of emitted frames to a consumer, such as a queue or a parser, rather than returning
+//! them to the caller
+
+use crate::{DeframeError, Deframer, Element};
+
+/// A consumer of frames, which is given each complete frame by [`Deframer::deframe_to`]
+pub trait FrameSink<T = u8> {
+    /// Accepts a complete frame, which is only borrowed for the duration of the call
+    fn accept(&mut self, frame: &[T]);
+}
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Passes every complete frame in `data_frame` to `sink`, as [`Deframer::deframe_each`] does
+    /// for a closure, including how errors are handled
+    pub fn deframe_to(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, sink: &mut impl FrameSink<T>) -> Result<(), DeframeError> {
+        self.deframe_each(data_frame, get_frame_end, |frame| sink.accept(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use super::FrameSink;
+    use crate::Deframer;
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    /// Records the length and first element of every frame
+    #[derive(Default)]
+    struct Recorder {
+        frames: [(usize, u8); 4],
+        count: usize,
+    }
+
+    impl FrameSink for Recorder {
+        fn accept(&mut self, frame: &[u8]) {
+            self.frames[self.count] = (frame.len(), frame[0]);
+            self.count += 1;
+        }
+    }
+
+    #[test]
+    fn passes_every_frame_to_the_sink() {
+        let mut deframer = Deframer::<4>::new();
+        let mut sink = Recorder::default();
+        deframer.deframe_to(&[0x01, FRAME_END, 0x02, 0x03, FRAME_END, 0x04], GET_FRAME_END, &mut sink).unwrap();
+        deframer.deframe_to(&[FRAME_END], GET_FRAME_END, &mut sink).unwrap();
+        assert_eq!(sink.frames[0..sink.count], [(2, 0x01), (3, 0x02), (2, 0x04)]);
+        assert!(deframer.is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+//! A deframer whose remainder is a caller-provided buffer, sized at runtime rather than by a const
+//! generic
+
+use crate::{append, assemble_frame, take_buffered_frame, DeframeError, Element, Overflow};
+
+/// Like [`crate::Deframer`], but the remainder is borrowed from the caller, so deframers with
+/// different buffer sizes share one instantiation of the deframing code. The remainder capacity
+/// is the length of the buffer, and each frame is written into a caller-provided slice.
+pub struct SliceDeframer<'a, T = u8> {
+    buffer: &'a mut [T],
+    remainder_length: usize,
+    dropped_bytes: usize,
+}
+
+impl<'a, T: Element> SliceDeframer<'a, T> {
+    /// Creates an empty deframer that buffers its remainder in `buffer`, which is zeroed
+    pub fn new(buffer: &'a mut [T]) -> Self {
+        buffer.fill(T::ZERO);
+        Self { buffer, remainder_length: 0, dropped_bytes: 0 }
+    }
+
+    /// Returns the elements that have been buffered but not yet emitted as part of a frame
+    pub fn remainder(&self) -> &[T] {
+        &self.buffer[0..self.remainder_length]
+    }
+
+    /// Returns the remainder capacity, which is the length of the buffer
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns true if nothing is buffered
+    pub fn is_empty(&self) -> bool {
+        self.remainder_length == 0
+    }
+
+    /// Discards any buffered remainder
+    pub fn reset(&mut self) {
+        self.buffer[0..self.remainder_length].fill(T::ZERO);
+        self.remainder_length = 0;
+    }
+
+    /// Returns the number of elements discarded on error, as with
+    /// [`crate::Deframer::dropped_bytes`]
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    /// Prepends any remainder from previous calls to `data_frame` and writes the data up to the
+    /// first frame end found by `get_frame_end` into the start of `out`, returning its length or
+    /// `None` if no frame end is found, in which case all of the data is buffered. Whatever
+    /// follows the frame end is buffered for the next call.
+    ///
+    /// A frame that doesn't fit in `out` is an [`Overflow::SingleFrameTooLarge`] error, and data
+    /// that doesn't fit in the buffer an [`Overflow::RemainderExhausted`] error. As with
+    /// [`crate::Deframer::deframe`], the remainder and all of `data_frame` are discarded on error.
+    pub fn deframe(&mut self, data_frame: &[T], out: &mut [T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+        let buffered = self.remainder_length + data_frame.len();
+        self.deframe_frame(data_frame, out, get_frame_end).inspect_err(|_| {
+            self.dropped_bytes = self.dropped_bytes.wrapping_add(buffered);
+            // A failed call may have left data past the remainder, so the whole buffer is cleared
+            self.buffer.fill(T::ZERO);
+            self.remainder_length = 0;
+        })
+    }
+
+    fn deframe_frame(&mut self, data_frame: &[T], out: &mut [T], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<usize>, DeframeError> {
+        // A frame end already in the remainder ends the frame first
+        if let Some(len) = take_buffered_frame(self.buffer, &mut self.remainder_length, out, false, &mut get_frame_end)? {
+            append(self.buffer, &mut self.remainder_length, data_frame, Overflow::RemainderExhausted)?;
+            return Ok(Some(len));
+        }
+        assemble_frame(self.buffer, &mut self.remainder_length, data_frame, out, false, get_frame_end)
+    }
+}
+
+impl<T> core::fmt::Debug for SliceDeframer<'_, T> {
+    /// Shows how much of the buffer is in use rather than its contents, which may be sensitive
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SliceDeframer")
+            .field("capacity", &self.buffer.len())
+            .field("remainder_length", &self.remainder_length)
+            .field("dropped_bytes", &self.dropped_bytes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use alloc::format;
+
+    use super::SliceDeframer;
+    use crate::{DeframeError, Overflow};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn deframes_into_a_borrowed_buffer() {
+        let mut buffer = [0xFF; 4];
+        let mut deframer = SliceDeframer::new(&mut buffer);
+        assert_eq!(deframer.capacity(), 4);
+
+        let mut out = [0; 8];
+        assert_eq!(deframer.deframe(&[0x01, 0x02], &mut out, GET_FRAME_END), Ok(None));
+        assert_eq!(deframer.remainder(), [0x01, 0x02]);
+
+        assert_eq!(deframer.deframe(&[FRAME_END, 0x03, FRAME_END, 0x04], &mut out, GET_FRAME_END), Ok(Some(3)));
+        assert_eq!(out[0..3], [0x01, 0x02, FRAME_END]);
+
+        // The frame left in the remainder is returned first
+        assert_eq!(deframer.deframe(&[0x05], &mut out, GET_FRAME_END), Ok(Some(2)));
+        assert_eq!(out[0..2], [0x03, FRAME_END]);
+        assert_eq!(deframer.remainder(), [0x04, 0x05]);
+
+        deframer.reset();
+        assert!(deframer.is_empty());
+        assert_eq!(buffer, [0; 4]);
+    }
+
+    #[test]
+    fn overflows_at_the_buffer_length() {
+        let mut buffer = [0; 2];
+        let mut deframer = SliceDeframer::new(&mut buffer);
+        let mut out = [0; 8];
+        let result = deframer.deframe(&[0x01, 0x02, 0x03], &mut out, GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::RemainderExhausted)));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 3);
+
+        let mut out = [0; 2];
+        let result = deframer.deframe(&[0x01, 0x02, FRAME_END], &mut out, GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::Overflow(Overflow::SingleFrameTooLarge)));
+    }
+
+    #[test]
+    fn debug_shows_the_buffer_use_but_not_its_contents() {
+        let mut buffer = [0; 4];
+        let mut deframer = SliceDeframer::new(&mut buffer);
+        deframer.deframe(&[0x2A, 0x2B], &mut [0; 4], GET_FRAME_END).unwrap();
+        let debug = format!("{:?}", deframer);
+        assert_eq!(debug, "SliceDeframer { capacity: 4, remainder_length: 2, dropped_bytes: 0 }");
+    }
+}
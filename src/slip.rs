@@ -0,0 +1,114 @@
+//! Serial Line Internet Protocol (SLIP, RFC 1055) framing, where frames are delimited by an END
+//! byte and any END or ESC bytes in the payload are escaped
+
+use crate::{DeframeError, Deframer, Frame, FrameEnd};
+
+/// The SLIP frame delimiter
+const END: u8 = 0xC0;
+/// Starts an escape sequence
+const ESC: u8 = 0xDB;
+/// Follows [`ESC`] to encode an [`END`] byte
+const ESC_END: u8 = 0xDC;
+/// Follows [`ESC`] to encode an [`ESC`] byte
+const ESC_ESC: u8 = 0xDD;
+
+impl<const N: usize, const BUF: usize> Deframer<N, u8, BUF> {
+    /// Deframes SLIP encoded data, splitting frames on the next END byte and de-escaping each one
+    /// into the returned buffer. The returned length is that of the decoded payload, with the
+    /// escape sequences and delimiter stripped.
+    ///
+    /// If a checksum is set it is verified against, and removed from, the decoded payload.
+    ///
+    /// A frame with an invalid escape sequence is counted in [`Deframer::dropped_bytes`], but
+    /// unlike an overflow the bytes buffered after it are kept.
+    pub fn deframe_slip(&mut self, data_frame: &[u8]) -> Result<Frame<N>, DeframeError> {
+        Ok(self.next_frame_slip(data_frame)?.unwrap_or_default())
+    }
+
+    /// Like [`Deframer::deframe_slip`], but returns `Ok(None)` while no complete frame is
+    /// available, so that a frame with an empty payload can be told apart
+    pub fn next_frame_slip(&mut self, data_frame: &[u8]) -> Result<Option<Frame<N>>, DeframeError> {
+        let encoded_len = self.deframe_frame(data_frame, None, FrameEnd::Raw(1), |iter| iter.position(|&x| x == END))?;
+
+        let mut data: [u8; N] = [0; N];
+        let encoded_len = match encoded_len {
+            Some(encoded_len) => encoded_len,
+            None => return Ok(None),
+        };
+
+        // Decode the frame without its trailing delimiter, then verify the decoded payload
+        let len = decode(&self.frame[0..encoded_len - 1], &mut data).and_then(|len| match self.checksum {
+            Some(checksum) => checksum.verify(&data[0..len]),
+            None => Ok(len),
+        });
+        let len = len.map_err(|error| {
+            self.dropped_bytes = self.dropped_bytes.wrapping_add(encoded_len);
+            self.record_error(error, data_frame.len(), 0)
+        })?;
+        data[len..N].fill(0);
+        Ok(Some(Frame::new(data, self.count_frame(len))))
+    }
+}
+
+/// Decodes a single SLIP encoded frame (excluding the delimiter) into `out`, returning the
+/// decoded length. `out` must be at least as long as `encoded`.
+fn decode(encoded: &[u8], out: &mut [u8]) -> Result<usize, DeframeError> {
+    let mut bytes = encoded.iter();
+    let mut write = 0;
+
+    while let Some(&byte) = bytes.next() {
+        out[write] = match byte {
+            ESC => match bytes.next() {
+                Some(&ESC_END) => END,
+                Some(&ESC_ESC) => ESC,
+                _ => return Err(DeframeError::InvalidEncoding),
+            },
+            byte => byte,
+        };
+        write += 1;
+    }
+
+    Ok(write)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeframeError, Deframer, Frame};
+
+    #[test]
+    fn decodes_slip_frames() {
+        let mut deframer = Deframer::<8>::new();
+        let (data, len) = deframer.deframe_slip(&[0x11, 0xDB, 0xDC, 0x22, 0xDB, 0xDD, 0xC0]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x11, 0xC0, 0x22, 0xDB]);
+
+        // The escape sequence is split across data frames
+        let (_data, len) = deframer.deframe_slip(&[0x33, 0xDB]).unwrap().into_parts();
+        assert_eq!(len, 0);
+        let (data, len) = deframer.deframe_slip(&[0xDC, 0xC0]).unwrap().into_parts();
+        assert_eq!(data[0..len], [0x33, 0xC0]);
+        assert!(deframer.is_empty());
+    }
+
+    #[test]
+    fn distinguishes_no_frame_from_an_empty_frame() {
+        let mut deframer = Deframer::<4>::new();
+        assert_eq!(deframer.next_frame_slip(&[0x11]).unwrap(), None);
+        assert_eq!(deframer.next_frame_slip(&[0xC0]).unwrap().map(Frame::into_parts), Some(([0x11, 0, 0, 0], 1)));
+
+        // SLIP senders commonly start each frame with an END to flush line noise
+        assert_eq!(deframer.next_frame_slip(&[0xC0]).unwrap().map(Frame::into_parts), Some(([0; 4], 0)));
+    }
+
+    #[test]
+    fn rejects_invalid_escape_sequences() {
+        let mut deframer = Deframer::<8>::new();
+        let result = deframer.deframe_slip(&[0x11, 0xDB, 0x22, 0xC0, 0x33]);
+        assert_eq!(result, Err(DeframeError::InvalidEncoding));
+        assert_eq!(deframer.dropped_bytes(), 4);
+        assert_eq!(deframer.remainder(), [0x33]);
+
+        // An escape at the very end of a frame is also invalid
+        let result = deframer.deframe_slip(&[0xDB, 0xC0]);
+        assert_eq!(result, Err(DeframeError::InvalidEncoding));
+    }
+}
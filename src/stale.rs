@@ -0,0 +1,68 @@
+//! Detection of partial frames that are never completed, without the deframer keeping time
+
+use crate::{Deframer, Element};
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Returns the number of deframing calls since the last frame was emitted, or since
+    /// construction or [`Deframer::reset`]. Calling at a steady rate, such as once per poll of a
+    /// UART, turns this into a coarse timer for how long the remainder has gone without a frame.
+    pub fn calls_since_last_frame(&self) -> usize {
+        self.calls_since_frame
+    }
+
+    /// Discards the remainder if at least `threshold` calls have passed without a frame being
+    /// emitted, returning true if a stale partial frame was discarded. The discarded elements are
+    /// counted in [`Deframer::dropped_bytes`].
+    pub fn flush_if_stale(&mut self, threshold: usize) -> bool {
+        if self.calls_since_frame < threshold || self.remainder_length == 0 {
+            return false;
+        }
+        self.dropped_bytes = self.dropped_bytes.wrapping_add(self.remainder_length);
+        self.clear_remainder();
+        self.calls_since_frame = 0;
+        true
+    }
+
+    /// Counts a deframing call, which is reset once a frame is emitted
+    pub(crate) fn count_call(&mut self) {
+        self.calls_since_frame = self.calls_since_frame.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use crate::Deframer;
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn counts_calls_since_the_last_frame() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.deframe(&[0x01], GET_FRAME_END).unwrap();
+        deframer.deframe(&[], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.calls_since_last_frame(), 2);
+
+        deframer.deframe(&[FRAME_END, 0x02], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.calls_since_last_frame(), 0);
+        deframer.deframe_delimited(&[0x03]).unwrap();
+        assert_eq!(deframer.calls_since_last_frame(), 1);
+    }
+
+    #[test]
+    fn flushes_stale_partial_frames() {
+        let mut deframer = Deframer::<8>::new();
+        assert!(!deframer.flush_if_stale(0));
+
+        deframer.deframe(&[0x01, 0x02], GET_FRAME_END).unwrap();
+        deframer.deframe(&[], GET_FRAME_END).unwrap();
+        assert!(!deframer.flush_if_stale(3));
+        deframer.deframe(&[], GET_FRAME_END).unwrap();
+        assert!(deframer.flush_if_stale(3));
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.dropped_bytes(), 2);
+        assert_eq!(deframer.calls_since_last_frame(), 0);
+    }
+}
@@ -0,0 +1,124 @@
+//! Running counters of how much data a deframer has processed, for link health telemetry
+
+use crate::{DeframeError, Deframer, Element};
+
+/// Counters of a deframer's activity since construction or [`Deframer::reset_stats`]. All of
+/// them wrap on overflow.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeframerStats {
+    /// The number of frames successfully deframed
+    pub frames: usize,
+    /// The number of elements passed to the deframer
+    pub bytes: usize,
+    /// The number of [`DeframeError::Overflow`] errors
+    pub overflows: usize,
+}
+
+impl DeframerStats {
+    /// Creates zeroed counters
+    pub const fn new() -> Self {
+        Self {
+            frames: 0,
+            bytes: 0,
+            overflows: 0,
+        }
+    }
+}
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Returns the running counters of frames, elements and overflows
+    pub fn stats(&self) -> DeframerStats {
+        self.stats
+    }
+
+    /// Zeroes the running counters, for example at the start of each reporting window
+    pub fn reset_stats(&mut self) {
+        self.stats = DeframerStats::new();
+    }
+
+    /// Returns the sequence number the next frame will be given, which is the number of frames
+    /// emitted since construction. Unlike [`DeframerStats::frames`] it is never reset, not even by
+    /// [`Deframer::reset`], and it wraps to zero after `usize::MAX`.
+    ///
+    /// Only emitted frames are numbered, so the numbers have no gaps even where frames were
+    /// dropped. Detecting lost data needs [`Deframer::dropped_bytes`], or sequence numbers added
+    /// by the sender.
+    pub fn frame_counter(&self) -> usize {
+        self.frame_counter
+    }
+
+    /// Like [`Deframer::deframe_each`], but `f` is also given the sequence number of each frame,
+    /// as counted by [`Deframer::frame_counter`]
+    pub fn deframe_each_numbered(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>, mut f: impl FnMut(usize, &[T])) -> Result<(), DeframeError> {
+        let mut number = self.frame_counter;
+        self.deframe_each(data_frame, get_frame_end, |frame| {
+            f(number, frame);
+            number = number.wrapping_add(1);
+        })
+    }
+
+    /// Counts `len` elements passed to the deframer
+    pub(crate) fn count_bytes(&mut self, len: usize) {
+        self.stats.bytes = self.stats.bytes.wrapping_add(len);
+    }
+
+    /// Counts a successfully deframed frame, returning its `len` unchanged
+    pub(crate) fn count_frame(&mut self, len: usize) -> usize {
+        self.stats.frames = self.stats.frames.wrapping_add(1);
+        self.frame_counter = self.frame_counter.wrapping_add(1);
+        self.calls_since_frame = 0;
+        len
+    }
+
+    /// Counts `error` if it is an overflow
+    pub(crate) fn count_error(&mut self, error: DeframeError) {
+        if let DeframeError::Overflow(_) = error {
+            self.stats.overflows = self.stats.overflows.wrapping_add(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use crate::{Deframer, DeframerStats};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn counts_frames_bytes_and_overflows() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, FRAME_END, 0x02], GET_FRAME_END).unwrap();
+        deframer.deframe(&[FRAME_END], GET_FRAME_END).unwrap();
+        deframer.deframe(&[0x03], GET_FRAME_END).unwrap();
+        assert!(deframer.deframe(&[0x04, 0x05, 0x06, 0x07], GET_FRAME_END).is_err());
+        for frame in deframer.deframe_all(&[0x08, FRAME_END, 0x09, FRAME_END], GET_FRAME_END) {
+            frame.unwrap();
+        }
+        assert_eq!(deframer.stats(), DeframerStats { frames: 4, bytes: 13, overflows: 1 });
+
+        deframer.reset_stats();
+        assert_eq!(deframer.stats(), DeframerStats::default());
+    }
+
+    #[test]
+    fn numbers_frames_since_construction() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe(&[0x01, FRAME_END], GET_FRAME_END).unwrap();
+        deframer.reset_stats();
+        deframer.reset();
+        assert_eq!(deframer.frame_counter(), 1);
+
+        let mut numbers = [0; 2];
+        let mut frames = 0;
+        deframer.deframe_each_numbered(&[0x02, FRAME_END, 0x03, FRAME_END], GET_FRAME_END, |number, _frame| {
+            numbers[frames] = number;
+            frames += 1;
+        }).unwrap();
+        assert_eq!(numbers, [1, 2]);
+        assert_eq!(deframer.frame_counter(), 3);
+    }
+}
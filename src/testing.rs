@@ -0,0 +1,68 @@
+//! Helpers for testing code built on a deframer against data arriving in arbitrary chunks,
+//! available in the crate's own tests and behind the `testing` feature
+
+use alloc::vec::Vec;
+
+use crate::{DeframeError, Deframer, Element};
+
+/// Feeds `stream` to `deframer` in chunks whose lengths are taken from `chunk_sizes` in turn,
+/// starting over once they run out, and returns every frame emitted as its own `Vec`, in order.
+/// An empty `chunk_sizes` feeds the whole stream as one chunk, and a size of zero feeds an empty
+/// chunk. Anything left over after the stream is fed stays buffered in `deframer`.
+///
+/// A chunk in which a frame fails is still processed to its end, as with
+/// [`Deframer::deframe_each`], but no further chunks are fed and the first error is returned.
+///
+/// # Panics
+///
+/// If `chunk_sizes` isn't empty but all of its sizes are zero, as the stream would never be fed
+pub fn deframe_in_chunks<const N: usize, T: Element, const BUF: usize>(deframer: &mut Deframer<N, T, BUF>, stream: &[T], chunk_sizes: &[usize], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Vec<Vec<T>>, DeframeError> {
+    assert!(chunk_sizes.is_empty() || chunk_sizes.iter().any(|&size| size > 0), "chunk sizes that are all zero");
+    let mut frames = Vec::new();
+    let mut sizes = chunk_sizes.iter().copied().cycle();
+    let mut rest = stream;
+    while !rest.is_empty() {
+        let (chunk, next) = rest.split_at(sizes.next().unwrap_or(rest.len()).min(rest.len()));
+        deframer.deframe_each(chunk, &mut get_frame_end, |frame| frames.push(frame.to_vec()))?;
+        rest = next;
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use alloc::vec;
+
+    use super::deframe_in_chunks;
+    use crate::{DeframeError, Deframer, Overflow};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    const STREAM: [u8; 8] = [0x01, FRAME_END, 0x02, 0x03, FRAME_END, FRAME_END, 0x04, 0x05];
+
+    #[test]
+    fn collects_the_same_frames_however_the_stream_is_split() {
+        let expected = vec![vec![0x01, FRAME_END], vec![0x02, 0x03, FRAME_END], vec![FRAME_END]];
+        for chunk_sizes in [&[][..], &[1], &[3], &[2, 0, 5], &[8]] {
+            let mut deframer = Deframer::<4>::new();
+            assert_eq!(deframe_in_chunks(&mut deframer, &STREAM, chunk_sizes, GET_FRAME_END).unwrap(), expected);
+            assert_eq!(deframer.remainder(), [0x04, 0x05]);
+        }
+    }
+
+    #[test]
+    fn stops_at_the_first_error() {
+        let mut deframer = Deframer::<2>::new();
+        let error = DeframeError::Overflow(Overflow::SingleFrameTooLarge);
+        assert_eq!(deframe_in_chunks(&mut deframer, &STREAM, &[2], GET_FRAME_END), Err(error));
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk sizes that are all zero")]
+    fn panics_on_chunk_sizes_that_are_all_zero() {
+        deframe_in_chunks(&mut Deframer::<4>::new(), &STREAM, &[0, 0], GET_FRAME_END).unwrap();
+    }
+}
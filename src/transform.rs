@@ -0,0 +1,158 @@
+//! A custom transform, such as unescaping, applied to each raw frame before it is returned
+
+use core::slice::Iter;
+
+use crate::{first_frame_end, DeframeError, Deframer, Element};
+
+/// Transforms a raw payload in place into the final payload, returning its length, see
+/// [`Deframer::set_transform`]
+pub type Transform<T> = fn(payload: &mut [T]) -> Result<usize, ()>;
+
+impl<const N: usize, T: Element, const BUF: usize> Deframer<N, T, BUF> {
+    /// Sets a transform to run over the payload of every completed frame, or removes it with
+    /// `None`. The transform is given the payload before the frame end and rewrites it in place,
+    /// returning the length of the final payload, which can't be longer than the raw one. It runs
+    /// before any checksum is verified, so that the checksum covers the transformed payload.
+    ///
+    /// A frame the transform rejects is dropped with `Err(DeframeError::InvalidEncoding)` and
+    /// counted in [`Deframer::dropped_bytes`], keeping any elements buffered after it. COBS, SLIP,
+    /// CR LF, bounded and length-prefixed deframing do their own decoding and aren't transformed.
+    pub fn set_transform(&mut self, transform: Option<Transform<T>>) {
+        self.transform = transform;
+    }
+
+    /// Sets an element that escapes the one after it, or removes it with `None`. A frame end
+    /// reported by `get_frame_end` that is escaped doesn't end the frame, even if the escape
+    /// arrived in an earlier data frame, so that a payload can carry escaped delimiters. An
+    /// escaped escape escapes nothing. The escapes are kept in the frame for the transform set
+    /// with [`Deframer::set_transform`] to remove.
+    ///
+    /// Only the methods that take `get_frame_end` and finish their frames as configured skip
+    /// escaped frame ends, the other framings have their own escaping.
+    pub fn set_escape(&mut self, escape: Option<T>) {
+        self.escape = escape;
+    }
+}
+
+/// Transforms the payload of the frame of length `len` in `out`, whose frame end is `end_len`
+/// elements long, keeping the frame end after the transformed payload. Returns the new length.
+pub(crate) fn transform_frame<T: Element>(out: &mut [T], len: usize, end_len: usize, transform: Transform<T>) -> Result<usize, DeframeError> {
+    let raw_len = len - end_len;
+    let payload_len = match transform(&mut out[0..raw_len]) {
+        Ok(payload_len) if payload_len <= raw_len => payload_len,
+        _ => return Err(DeframeError::InvalidEncoding),
+    };
+    // Move the frame end down after the payload, [payload...frame end]
+    out.copy_within(raw_len..len, payload_len);
+    let new_len = payload_len + end_len;
+    out[new_len..len].fill(T::ZERO);
+    Ok(new_len)
+}
+
+/// Wraps `get_frame_end` so that it skips frame ends preceded by an unescaped `escape`, if set.
+/// Searches from the start of `data_frame` take into account an escape at the end of the
+/// `remainder` in front of it, any other search starts after a frame end or at the start of the
+/// remainder, so it starts unescaped. An out of range position is reported as `usize::MAX`.
+pub(crate) fn skip_escaped<T: Element>(escape: Option<T>, remainder: &[T], data_frame: &[T], forward: bool, mut get_frame_end: impl FnMut(&mut Iter<T>) -> Option<usize>) -> impl FnMut(&mut Iter<T>) -> Option<usize> {
+    let data_start = data_frame.as_ptr();
+    let data_escaped = escape.is_some_and(|escape| remainder.iter().fold(false, |escaped, &x| !escaped && x == escape));
+    move |iter| {
+        let escape = match escape {
+            Some(escape) => escape,
+            None => return get_frame_end(iter),
+        };
+        let data = iter.as_slice();
+        let mut escaped = data_escaped && data.as_ptr() == data_start;
+        let mut start = 0;
+        loop {
+            let pos = match first_frame_end(&data[start..data.len()], forward, &mut get_frame_end) {
+                Ok(Some(pos)) => start + pos,
+                Ok(None) => return None,
+                Err(_) => return Some(usize::MAX),
+            };
+            for &x in &data[start..pos] {
+                escaped = !escaped && x == escape;
+            }
+            if !escaped {
+                return Some(pos);
+            }
+            // An escaped frame end can't escape the element after it
+            escaped = false;
+            start = pos + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::slice::Iter;
+
+    use crate::{DeframeError, Deframer};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    /// Removes backslash escapes in place, rejecting a trailing backslash
+    fn unescape(payload: &mut [u8]) -> Result<usize, ()> {
+        let (mut len, mut i) = (0, 0);
+        while i < payload.len() {
+            if payload[i] == b'\\' {
+                i += 1;
+            }
+            payload[len] = *payload.get(i).ok_or(())?;
+            len += 1;
+            i += 1;
+        }
+        Ok(len)
+    }
+
+    #[test]
+    fn transforms_each_frame() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_transform(Some(unescape));
+        let (data, len) = deframer.deframe(b"a\\\\b\\c\n", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"a\\bc\n");
+        assert_eq!(data[len..8], [0; 3]);
+
+        deframer.set_strip_delimiter(true);
+        let (data, len) = deframer.deframe(b"\\d\n", GET_FRAME_END).unwrap().into_parts();
+        assert_eq!(data[0..len], *b"d");
+    }
+
+    #[test]
+    fn rejects_frames_the_transform_fails_on() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_transform(Some(unescape));
+        let result = deframer.deframe(b"ab\\\nc", GET_FRAME_END);
+        assert_eq!(result, Err(DeframeError::InvalidEncoding));
+        assert_eq!(deframer.dropped_bytes(), 4);
+        assert_eq!(deframer.remainder(), b"c");
+    }
+
+    #[test]
+    fn skips_escaped_frame_ends() {
+        let mut deframer = Deframer::<8>::new();
+        deframer.set_transform(Some(unescape));
+        deframer.set_escape(Some(b'\\'));
+        let frame = deframer.next_frame(b"a\\\nb\n", GET_FRAME_END).unwrap().unwrap();
+        assert_eq!(*frame, *b"a\nb\n");
+
+        // The escape arrives in an earlier data frame than the frame end it escapes
+        assert_eq!(deframer.next_frame(b"c\\", GET_FRAME_END), Ok(None));
+        let frame = deframer.next_frame(b"\nd\n", GET_FRAME_END).unwrap().unwrap();
+        assert_eq!(*frame, *b"c\nd\n");
+
+        // An escaped escape doesn't escape the frame end after it
+        let frame = deframer.next_frame(b"e\\\\\nf", GET_FRAME_END).unwrap().unwrap();
+        assert_eq!(*frame, *b"e\\\n");
+        assert_eq!(deframer.remainder(), b"f");
+
+        let frames: Vec<_> = deframer.deframe_all(b"\\\ng\nh\\", GET_FRAME_END).map(Result::unwrap).collect();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(*frames[0], *b"f\ng\n");
+        let frames: Vec<_> = deframer.deframe_all(b"\n\n", GET_FRAME_END).map(Result::unwrap).collect();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(*frames[0], *b"h\n\n");
+    }
+}
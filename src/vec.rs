@@ -0,0 +1,160 @@
+//! A heap allocated deframer for targets with an allocator, whose buffer grows to fit any frame
+
+use alloc::vec::Vec;
+
+use crate::{first_frame_end, DeframeError, Element};
+
+/// Like [`crate::Deframer`], but the remainder grows as needed rather than being limited to a
+/// fixed capacity, and each frame is returned in its own `Vec`. Deframing can therefore never
+/// overflow, so set a maximum frame length with [`DeframerVec::set_max_frame_len`] to keep a
+/// sender that never ends its frame from using up the heap.
+#[derive(Clone, Debug, Default)]
+pub struct DeframerVec<T = u8> {
+    remainder: Vec<T>,
+    max_frame_len: Option<usize>,
+}
+
+impl<T: Element> DeframerVec<T> {
+    /// Creates an empty deframer, which doesn't allocate until data is buffered
+    pub const fn new() -> Self {
+        Self {
+            remainder: Vec::new(),
+            max_frame_len: None,
+        }
+    }
+
+    /// Returns the elements that have been buffered but not yet emitted as part of a frame
+    pub fn remainder(&self) -> &[T] {
+        &self.remainder
+    }
+
+    /// Discards any buffered remainder
+    pub fn reset(&mut self) {
+        self.remainder.clear();
+    }
+
+    /// Sets the longest frame that is accepted, not counting its frame end, or `None` to accept
+    /// frames of any length, as with [`crate::Deframer::set_max_frame_len`].
+    ///
+    /// A longer frame is dropped with [`DeframeError::FrameTooLong`], keeping whatever follows it.
+    /// A remainder that grows longer than the maximum without a frame end is discarded with the
+    /// same error, so the remainder never holds much more than the maximum and one data frame.
+    pub fn set_max_frame_len(&mut self, max_frame_len: Option<usize>) {
+        self.max_frame_len = max_frame_len;
+    }
+
+    /// Prepends any remainder from previous calls to `data_frame` and returns the data up to the
+    /// first frame end found by `get_frame_end`, as with [`crate::Deframer::deframe`]. Whatever follows
+    /// the frame end is buffered for the next call. Returns `None` if no frame end is found, in
+    /// which case all of the data is buffered.
+    ///
+    /// Any frame end already in the remainder, such as after a data frame holding several frames,
+    /// ends the frame first, so frames are returned in order by the following calls.
+    ///
+    /// If `get_frame_end` returns a position outside of its data, the remainder and `data_frame`
+    /// are discarded with `Err(DeframeError::InvalidFrameEnd)`. Frames longer than the maximum
+    /// frame length are dropped with `Err(DeframeError::FrameTooLong)`.
+    pub fn deframe(&mut self, data_frame: &[T], get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Vec<T>>, DeframeError> {
+        let frame = self.deframe_frame(data_frame, get_frame_end).inspect_err(|_| self.remainder.clear())?;
+        let max_frame_len = match self.max_frame_len {
+            Some(max_frame_len) => max_frame_len,
+            None => return Ok(frame),
+        };
+        match frame {
+            Some(frame) if frame.len() - 1 > max_frame_len => Err(DeframeError::FrameTooLong),
+            None if self.remainder.len() > max_frame_len => {
+                self.remainder.clear();
+                Err(DeframeError::FrameTooLong)
+            }
+            frame => Ok(frame),
+        }
+    }
+
+    fn deframe_frame(&mut self, data_frame: &[T], mut get_frame_end: impl FnMut(&mut core::slice::Iter<T>) -> Option<usize>) -> Result<Option<Vec<T>>, DeframeError> {
+        if let Some(pos) = first_frame_end(&self.remainder, false, &mut get_frame_end)? {
+            let rest = self.remainder.split_off(pos + 1);
+            let frame = core::mem::replace(&mut self.remainder, rest);
+            self.remainder.extend_from_slice(data_frame);
+            return Ok(Some(frame));
+        }
+
+        let frame_end_pos = match first_frame_end(data_frame, false, get_frame_end)? {
+            Some(pos) => pos + 1,
+            None => {
+                self.remainder.extend_from_slice(data_frame);
+                return Ok(None);
+            }
+        };
+
+        // [remainder...data up to the frame end]
+        let mut frame = core::mem::take(&mut self.remainder);
+        frame.extend_from_slice(&data_frame[0..frame_end_pos]);
+        self.remainder.extend_from_slice(&data_frame[frame_end_pos..data_frame.len()]);
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+
+    use super::DeframerVec;
+    use crate::DeframeError;
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.rposition(|&x| x == FRAME_END);
+
+    #[test]
+    fn deframes_frames_of_any_length() {
+        let mut deframer = DeframerVec::new();
+        for _ in 0..100 {
+            assert_eq!(deframer.deframe(&[0x01; 64], GET_FRAME_END), Ok(None));
+        }
+        assert_eq!(deframer.remainder().len(), 6400);
+
+        let frame = deframer.deframe(&[FRAME_END, 0x02], GET_FRAME_END).unwrap().unwrap();
+        assert_eq!(frame.len(), 6401);
+        assert_eq!(frame.last(), Some(&FRAME_END));
+        assert_eq!(deframer.remainder(), [0x02]);
+    }
+
+    #[test]
+    fn drains_several_frames_from_one_chunk_in_order() {
+        let mut deframer = DeframerVec::new();
+        assert_eq!(deframer.deframe(b"a\nb\nc", GET_FRAME_END).unwrap().unwrap(), b"a\n");
+        assert_eq!(deframer.deframe(b"\n", GET_FRAME_END).unwrap().unwrap(), b"b\n");
+        assert_eq!(deframer.deframe(&[], GET_FRAME_END).unwrap().unwrap(), b"c\n");
+        assert_eq!(deframer.deframe(&[], GET_FRAME_END), Ok(None));
+    }
+
+    #[test]
+    fn ends_frames_at_the_earliest_frame_end_found_from_the_front() {
+        let mut deframer = DeframerVec::new();
+        let get_frame_end = |iter: &mut Iter<u8>| iter.position(|&x| x == FRAME_END);
+        assert_eq!(deframer.deframe(b"a\nb\nc", get_frame_end).unwrap().unwrap(), b"a\n");
+        assert_eq!(deframer.remainder(), b"b\nc");
+        assert_eq!(deframer.deframe(&[], get_frame_end).unwrap().unwrap(), b"b\n");
+    }
+
+    #[test]
+    fn rejects_frame_ends_outside_of_the_data() {
+        let mut deframer = DeframerVec::new();
+        assert_eq!(deframer.deframe(&[0x01], GET_FRAME_END), Ok(None));
+        assert_eq!(deframer.deframe(&[0x02], |iter| Some(iter.len())), Err(DeframeError::InvalidFrameEnd));
+        assert_eq!(deframer.remainder(), []);
+    }
+
+    #[test]
+    fn caps_the_remainder_at_the_maximum_frame_length() {
+        let mut deframer = DeframerVec::new();
+        deframer.set_max_frame_len(Some(4));
+        assert_eq!(deframer.deframe(b"abc", GET_FRAME_END), Ok(None));
+        assert_eq!(deframer.deframe(b"de", GET_FRAME_END), Err(DeframeError::FrameTooLong));
+        assert_eq!(deframer.remainder(), []);
+
+        // A frame at the maximum is accepted, a longer one is dropped keeping what follows it
+        assert_eq!(deframer.deframe(b"abcd\n", GET_FRAME_END).unwrap().unwrap(), b"abcd\n");
+        assert_eq!(deframer.deframe(b"abcde\nf", GET_FRAME_END), Err(DeframeError::FrameTooLong));
+        assert_eq!(deframer.remainder(), b"f");
+    }
+}
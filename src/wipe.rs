@@ -0,0 +1,139 @@
+//! Wiping of buffered data with the `zeroize` crate, for payloads that mustn't linger in memory
+//!
+//! Elements are zeroed as they leave the remainder, so the remainder only ever holds data not yet
+//! emitted. What is wiped here is that data along with the copy of the last frame kept in the
+//! deframer's own frame buffer, using writes that the compiler can't optimise away.
+
+use zeroize::Zeroize;
+
+use crate::{Deframer, Element};
+
+impl<const N: usize, T: Element + Zeroize, const BUF: usize> Zeroize for Deframer<N, T, BUF> {
+    /// Wipes the remainder and the deframer's own frame buffer, leaving the deframer empty as
+    /// after [`Deframer::reset`]. The configuration is kept.
+    fn zeroize(&mut self) {
+        self.remainder.zeroize();
+        self.frame.zeroize();
+        self.reset();
+    }
+}
+
+/// Holds a [`Deframer`] and wipes its buffers when dropped, as with [`Zeroize::zeroize`], so
+/// that no partial frame is left behind in memory the deframer no longer owns. A deframer is
+/// `Copy` and so can't implement `Drop` itself; this dereferences to the deframer it holds.
+#[derive(Clone, Debug)]
+pub struct WipingDeframer<const N: usize, T: Element + Zeroize = u8, const BUF: usize = N>(Deframer<N, T, BUF>);
+
+impl<const N: usize, T: Element + Zeroize, const BUF: usize> WipingDeframer<N, T, BUF> {
+    /// Wraps `deframer`, to be wiped when dropped
+    pub fn new(deframer: Deframer<N, T, BUF>) -> Self {
+        Self(deframer)
+    }
+}
+
+impl<const N: usize, T: Element + Zeroize, const BUF: usize> core::ops::Deref for WipingDeframer<N, T, BUF> {
+    type Target = Deframer<N, T, BUF>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize, T: Element + Zeroize, const BUF: usize> core::ops::DerefMut for WipingDeframer<N, T, BUF> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: usize, T: Element + Zeroize, const BUF: usize> Drop for WipingDeframer<N, T, BUF> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice::Iter;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use zeroize::Zeroize;
+
+    use crate::{Deframer, Element, WipingDeframer};
+
+    const FRAME_END: u8 = 0x0A;
+    const GET_FRAME_END: fn(iter: &mut Iter<u8>) -> Option<usize> = |iter| iter.position(|&x| x == FRAME_END);
+
+    #[test]
+    fn wipes_buffered_data() {
+        let mut deframer = Deframer::<4>::new();
+        deframer.deframe_each(&[0x01, FRAME_END, 0x02], GET_FRAME_END, |_| {}).unwrap();
+        assert_eq!(deframer.frame[0..2], [0x01, FRAME_END]);
+        assert_eq!(deframer.remainder[0..1], [0x02]);
+
+        deframer.zeroize();
+        assert_eq!(deframer.remainder, [0; 4]);
+        assert_eq!(deframer.frame, [0; 4]);
+        assert!(deframer.is_empty());
+        assert_eq!(deframer.deframe_slice(&[FRAME_END], GET_FRAME_END).unwrap(), [FRAME_END]);
+    }
+
+    #[test]
+    fn zeroes_the_consumed_remainder_after_each_frame() {
+        let mut deframer = Deframer::<4, u8, 8>::new();
+        deframer.deframe(&[0x01, 0x02, 0x03, FRAME_END, 0x04, FRAME_END, 0x05], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder, [0x04, FRAME_END, 0x05, 0, 0, 0, 0, 0]);
+        deframer.deframe(&[], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder, [0x05, 0, 0, 0, 0, 0, 0, 0]);
+        deframer.deframe(&[0x06, FRAME_END], GET_FRAME_END).unwrap();
+        assert_eq!(deframer.remainder, [0; 8]);
+    }
+
+    /// The number of non-zero [`Tracked`] elements wiped
+    static WIPED: AtomicUsize = AtomicUsize::new(0);
+
+    /// An element that counts how many non-zero values are wiped
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Tracked(u8);
+
+    impl Element for Tracked {
+        const ZERO: Self = Tracked(0);
+    }
+
+    impl Zeroize for Tracked {
+        fn zeroize(&mut self) {
+            if self.0 != 0 {
+                WIPED.fetch_add(1, Ordering::Relaxed);
+            }
+            self.0 = 0;
+        }
+    }
+
+    #[test]
+    fn wipes_on_drop() {
+        let get_frame_end = |iter: &mut Iter<Tracked>| iter.position(|&x| x == Tracked(FRAME_END));
+        {
+            let mut deframer = WipingDeframer::new(Deframer::<4, Tracked>::new());
+            let frame = deframer.deframe_slice(&[Tracked(0x01), Tracked(FRAME_END), Tracked(0x02)], get_frame_end).unwrap();
+            assert_eq!(frame, [Tracked(0x01), Tracked(FRAME_END)]);
+            assert_eq!(WIPED.load(Ordering::Relaxed), 0);
+        }
+        // The frame left in the frame buffer and the partial frame in the remainder
+        assert_eq!(WIPED.load(Ordering::Relaxed), 3);
+    }
+
+    /// An element that can't be wiped
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Plain(u8);
+
+    impl Element for Plain {
+        const ZERO: Self = Plain(0);
+    }
+
+    #[test]
+    fn deframes_elements_that_cannot_be_wiped() {
+        let get_frame_end = |iter: &mut Iter<Plain>| iter.position(|&x| x == Plain(FRAME_END));
+        let mut deframer = Deframer::<4, Plain>::new();
+        let frame = deframer.deframe_slice(&[Plain(0x01), Plain(FRAME_END)], get_frame_end).unwrap();
+        assert_eq!(frame, [Plain(0x01), Plain(FRAME_END)]);
+    }
+}